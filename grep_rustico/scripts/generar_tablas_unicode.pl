@@ -0,0 +1,100 @@
+#!/usr/bin/perl
+#Genera src/tablas_unicode.rs a partir de la Unicode Character Database que trae Perl
+#(Unicode::UCD). La categoría general sale de UnicodeData.txt y la escritura de Scripts.txt.
+#Uso, desde grep_rustico:
+#    perl scripts/generar_tablas_unicode.pl > src/tablas_unicode.rs
+#Se verifica que la versión de Unicode sea la 14.0.0, que es la de las tablas del repositorio.
+use strict;
+use warnings;
+use Unicode::UCD qw(prop_invmap);
+
+my $VERSION = "14.0.0";
+
+die "Se esperaba Unicode $VERSION y Perl trae " . Unicode::UCD::UnicodeVersion() . "\n"
+    unless Unicode::UCD::UnicodeVersion() eq $VERSION;
+
+#Devuelve los rangos (inicio, fin, valor) de una propiedad, sin los valores que se omiten.
+sub rangos {
+    my ($propiedad, $incluido) = @_;
+    my ($lista, $mapa) = prop_invmap($propiedad);
+    my @rangos;
+    for my $i (0 .. $#$lista - 1) {
+        my $valor = $mapa->[$i];
+        next unless $incluido->($valor);
+        push @rangos, [$lista->[$i], $lista->[$i + 1] - 1, $valor];
+    }
+    return @rangos;
+}
+
+sub imprimir_tabla {
+    my ($nombre, @rangos) = @_;
+    print "const $nombre: &[(u32, u32, &str)] = &[\n";
+    printf "    (0x%X, 0x%X, \"%s\"),\n", @$_ for @rangos;
+    print "];\n";
+}
+
+#Los surrogates (Cs) no pueden ser un char de Rust, por lo que no se incluyen.
+my @categorias = rangos("General_Category", sub { $_[0] ne "Cn" && $_[0] ne "Cs" });
+#Se incluyen todas las escrituras de Scripts.txt. Los caracteres que no aparecen en ese archivo
+#son de la escritura Unknown, que no se incluye.
+my @escrituras = rangos("Script", sub { $_[0] ne "Unknown" });
+my %escrituras = map { $_->[2] => 1 } @escrituras;
+my $lista_escrituras = join "", map { "    \"$_\",\n" } sort keys %escrituras;
+
+print <<"RUST";
+//!Tablas de propiedades de Unicode que utilizan las clases \\p{...} y \\P{...}.
+//!Fueron generadas a partir de la Unicode Character Database $VERSION: la categoría general
+//!de cada caracter sale de UnicodeData.txt y su escritura sale de Scripts.txt.
+//!Para volver a generarlas, desde grep_rustico:
+//!    perl scripts/generar_tablas_unicode.pl > src/tablas_unicode.rs
+
+///Categorías generales que se pueden indicar, con una letra (L) o con dos (Lu).
+pub(crate) const CATEGORIAS: &[&str] = &[
+    "L", "Lu", "Ll", "Lt", "Lm", "Lo", "M", "Mn", "Mc", "Me", "N", "Nd", "Nl", "No", "P", "Pc",
+    "Pd", "Ps", "Pe", "Pi", "Pf", "Po", "S", "Sm", "Sc", "Sk", "So", "Z", "Zs", "Zl", "Zp", "C",
+    "Cc", "Cf", "Co", "Cn",
+];
+
+///Escrituras que se pueden indicar: todas las de Scripts.txt, incluidas Common e Inherited.
+pub(crate) const ESCRITURAS: &[&str] = &[
+$lista_escrituras];
+
+///Rangos de caracteres (inicio y fin inclusive) con su categoría general.
+///Los caracteres que no aparecen no están asignados (categoría Cn).
+RUST
+imprimir_tabla("TABLA_CATEGORIAS", @categorias);
+print <<'RUST';
+
+///Rangos de caracteres (inicio y fin inclusive) con la escritura a la que pertenecen.
+///Los caracteres que no aparecen no pertenecen a ninguna escritura (Unknown).
+RUST
+imprimir_tabla("TABLA_ESCRITURAS", @escrituras);
+print <<'RUST';
+
+///Busca en una tabla de rangos ordenados el valor del rango que contiene al caracter.
+fn buscar_en_tabla(tabla: &[(u32, u32, &'static str)], c: char) -> Option<&'static str> {
+    let codigo = c as u32;
+    tabla
+        .binary_search_by(|(inicio, fin, _)| {
+            if *fin < codigo {
+                std::cmp::Ordering::Less
+            } else if *inicio > codigo {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .ok()
+        .map(|posicion| tabla[posicion].2)
+}
+
+///Devuelve la categoría general (de dos letras) a la que pertenece el caracter.
+pub(crate) fn categoria_general(c: char) -> &'static str {
+    buscar_en_tabla(TABLA_CATEGORIAS, c).unwrap_or("Cn")
+}
+
+///Devuelve la escritura a la que pertenece el caracter, si tiene una.
+pub(crate) fn escritura(c: char) -> Option<&'static str> {
+    buscar_en_tabla(TABLA_ESCRITURAS, c)
+}
+RUST
@@ -0,0 +1,215 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::mayusculas::plegar;
+
+const RAIZ: usize = 0;
+
+///Representa un estado del autómata: el prefijo de algún patrón leído hasta el momento.
+#[derive(Debug, Default)]
+struct Nodo {
+    hijos: HashMap<char, usize>,
+    falla: usize,
+    es_final: bool,
+    ///Cantidad de caracteres del patrón más largo que termina en este nodo, si es final.
+    largo: usize,
+}
+
+///Representa un autómata de Aho-Corasick para buscar muchas cadenas fijas a la vez,
+///como con la opción -F. Cada línea se recorre una sola vez, sin importar la cantidad
+///de patrones, por lo que el tiempo de búsqueda no crece con ella.
+///
+///Los patrones se guardan en un árbol de prefijos, y cada nodo tiene un enlace de falla
+///al nodo del prefijo más largo que también es sufijo de su camino, para seguir
+///la búsqueda sin volver atrás en el texto cuando un caracter no continúa ningún patrón.
+#[derive(Debug)]
+pub struct AhoCorasick {
+    nodos: Vec<Nodo>,
+    ignorar_mayusculas: bool,
+}
+
+impl AhoCorasick {
+    ///Crea el autómata a partir de los patrones, que se toman como cadenas fijas.
+    ///Un patrón vacío coincide con cualquier línea. Si se indica que se ignoren
+    ///las mayúsculas, los patrones y el texto se comparan plegados.
+    pub fn new(patrones: &[String], ignorar_mayusculas: bool) -> Self {
+        let mut automata = AhoCorasick {
+            nodos: vec![Nodo::default()],
+            ignorar_mayusculas,
+        };
+        for patron in patrones {
+            automata.agregar_patron(patron);
+        }
+        automata.calcular_fallas();
+        automata
+    }
+
+    fn normalizar(&self, c: char) -> char {
+        if self.ignorar_mayusculas {
+            plegar(c)
+        } else {
+            c
+        }
+    }
+
+    ///Agrega el camino del patrón al árbol de prefijos y marca su último nodo como final.
+    fn agregar_patron(&mut self, patron: &str) {
+        let mut actual = RAIZ;
+        for c in patron.chars() {
+            let c = self.normalizar(c);
+            actual = match self.nodos[actual].hijos.get(&c) {
+                Some(&siguiente) => siguiente,
+                None => {
+                    self.nodos.push(Nodo::default());
+                    let nuevo = self.nodos.len() - 1;
+                    self.nodos[actual].hijos.insert(c, nuevo);
+                    nuevo
+                }
+            };
+        }
+        self.nodos[actual].es_final = true;
+        self.nodos[actual].largo = patron.chars().count();
+    }
+
+    ///Calcula los enlaces de falla recorriendo el árbol a lo ancho, de forma que el enlace
+    ///de cada nodo ya está calculado cuando se procesan sus hijos. Un nodo también es final
+    ///si lo es el nodo al que apunta su enlace, porque ese patrón termina en el mismo lugar.
+    ///Si el nodo no era final, el largo del patrón es el del nodo del enlace.
+    fn calcular_fallas(&mut self) {
+        let mut pendientes: VecDeque<usize> = self.nodos[RAIZ].hijos.values().copied().collect();
+        while let Some(actual) = pendientes.pop_front() {
+            let hijos: Vec<(char, usize)> = self.nodos[actual]
+                .hijos
+                .iter()
+                .map(|(&c, &hijo)| (c, hijo))
+                .collect();
+            for (c, hijo) in hijos {
+                let falla = self.transicion(self.nodos[actual].falla, c);
+                self.nodos[hijo].falla = falla;
+                if !self.nodos[hijo].es_final {
+                    self.nodos[hijo].largo = self.nodos[falla].largo;
+                }
+                self.nodos[hijo].es_final |= self.nodos[falla].es_final;
+                pendientes.push_back(hijo);
+            }
+        }
+    }
+
+    ///Devuelve el estado al que se llega leyendo el caracter desde el estado indicado,
+    ///siguiendo los enlaces de falla hasta encontrar uno que continúe con ese caracter.
+    fn transicion(&self, mut estado: usize, c: char) -> usize {
+        loop {
+            if let Some(&siguiente) = self.nodos[estado].hijos.get(&c) {
+                return siguiente;
+            }
+            if estado == RAIZ {
+                return RAIZ;
+            }
+            estado = self.nodos[estado].falla;
+        }
+    }
+
+    ///Verifica si alguno de los patrones aparece en la línea.
+    pub fn es_valida(&self, linea: &str) -> bool {
+        if self.nodos[RAIZ].es_final {
+            return true;
+        }
+        let mut estado = RAIZ;
+        for c in linea.chars() {
+            estado = self.transicion(estado, self.normalizar(c));
+            if self.nodos[estado].es_final {
+                return true;
+            }
+        }
+        false
+    }
+
+    ///Devuelve las posiciones en bytes (inicio, fin) en las que aparece algún patrón en el texto,
+    ///como con la opción -U. En cada posición en la que terminan patrones se indica el más largo.
+    ///Un patrón vacío aparece en cada posición del texto.
+    pub fn coincidencias(&self, texto: &str) -> Vec<(usize, usize)> {
+        let mut inicios: Vec<usize> = texto.char_indices().map(|(i, _)| i).collect();
+        inicios.push(texto.len());
+        let mut coincidencias = Vec::new();
+        if self.nodos[RAIZ].es_final {
+            coincidencias.push((0, 0));
+        }
+        let mut estado = RAIZ;
+        for (leidos, c) in texto.chars().enumerate() {
+            estado = self.transicion(estado, self.normalizar(c));
+            let fin = inicios[leidos + 1];
+            if self.nodos[estado].es_final {
+                let largo = self.nodos[estado].largo;
+                coincidencias.push((inicios[leidos + 1 - largo], fin));
+            }
+        }
+        coincidencias
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_un_patron() {
+        let automata = AhoCorasick::new(&["a.b".to_string()], false);
+        assert!(automata.es_valida("xxa.bxx"));
+        assert!(!automata.es_valida("axb"));
+    }
+
+    #[test]
+    fn test02_varios_patrones() {
+        let automata = AhoCorasick::new(
+            &[
+                "he".to_string(),
+                "she".to_string(),
+                "his".to_string(),
+                "hers".to_string(),
+            ],
+            false,
+        );
+        assert!(automata.es_valida("ushers"));
+        assert!(automata.es_valida("ahishe"));
+        assert!(!automata.es_valida("hxsx"));
+    }
+
+    #[test]
+    fn test03_patron_contenido_en_otro() {
+        let automata = AhoCorasick::new(&["abcd".to_string(), "bc".to_string()], false);
+        assert!(automata.es_valida("abce"));
+        let automata = AhoCorasick::new(&["aab".to_string()], false);
+        assert!(automata.es_valida("aaab"));
+    }
+
+    #[test]
+    fn test04_patron_vacio_y_sin_patrones() {
+        assert!(AhoCorasick::new(&["".to_string()], false).es_valida("x"));
+        assert!(!AhoCorasick::new(&[], false).es_valida("x"));
+    }
+
+    #[test]
+    fn test05_ignorar_mayusculas_y_utf8() {
+        let automata = AhoCorasick::new(&["ÑANDÚ".to_string(), "[id-42]".to_string()], true);
+        assert!(automata.es_valida("un ñandú"));
+        assert!(automata.es_valida("clave [ID-42]"));
+        assert!(!automata.es_valida("nandu"));
+    }
+
+    #[test]
+    fn test06_muchos_patrones() {
+        let lista: Vec<String> = (0..5000).map(|i| format!("ID{:05};", i)).collect();
+        let automata = AhoCorasick::new(&lista, false);
+        assert!(automata.es_valida("pedido ID04999; listo"));
+        assert!(!automata.es_valida("pedido ID05000; listo"));
+    }
+
+    #[test]
+    fn test07_coincidencias_en_todo_el_texto() {
+        let automata = AhoCorasick::new(&["b\nc".to_string(), "ñA".to_string()], true);
+        assert_eq!(automata.coincidencias("ab\nc Ña"), vec![(1, 4), (5, 8)]);
+        let automata = AhoCorasick::new(&["abc".to_string(), "b".to_string()], false);
+        assert_eq!(automata.coincidencias("xabc"), vec![(2, 3), (1, 4)]);
+        let automata = AhoCorasick::new(&["".to_string()], false);
+        assert_eq!(automata.coincidencias("ab"), vec![(0, 0), (1, 1), (2, 2)]);
+    }
+}
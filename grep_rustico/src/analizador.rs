@@ -0,0 +1,933 @@
+use std::collections::HashSet;
+use std::str::Chars;
+
+use crate::ast::{NodoAst, TipoDeNodo};
+use crate::caracter::Caracter;
+use crate::clase_char::ClaseChar;
+use crate::configuracion::Configuracion;
+use crate::dialecto::Dialecto;
+use crate::error_en_expresion::ErrorEnExpresion;
+use crate::errors::Error;
+use crate::grupo::TipoDeGrupo;
+use crate::modificadores::Modificadores;
+use crate::repeticion::Repeticion;
+use crate::simbolo::Simbolo;
+use crate::tablas_unicode::{CATEGORIAS, ESCRITURAS};
+
+///Caracteres especiales que se utilizan en las expresiones regulares.
+const CORCHETE_ABIERTO: char = '[';
+const CORCHETE_CERRADO: char = ']';
+const LLAVE_ABIERTA: char = '{';
+const LLAVE_CERRADA: char = '}';
+const LLAVE_CERRADA_BASICA: &str = "\\}";
+const PARENTESIS_CERRADO_BASICO: &str = "\\)";
+const FUNCION_OR_BASICA: &str = "\\|";
+const ASTERISCO: char = '*';
+const INTERROGACION: char = '?';
+const MAS: char = '+';
+const PUNTO: char = '.';
+const BARRA: char = '\\';
+const DOLAR: char = '$';
+const CARET: char = '^';
+const INDICADOR_CLASE: char = ':';
+const FIN_CLASE_CON_NOMBRE: &str = ":]";
+const SEPARADOR_RANGO: char = '-';
+const SEPARADOR_LLAVE: char = ',';
+const FUNCION_OR: char = '|';
+const PARENTESIS_ABIERTO: char = '(';
+const PARENTESIS_CERRADO: char = ')';
+const SIN_CAPTURA: char = ':';
+const INDICADOR_NOMBRE: char = 'P';
+const NOMBRE_ABIERTO: char = '<';
+const NOMBRE_CERRADO: char = '>';
+const ATOMICO: char = '>';
+const LIMITE_PALABRA: char = 'b';
+const NO_LIMITE_PALABRA: char = 'B';
+const INICIO_PALABRA: char = '<';
+const FIN_PALABRA: char = '>';
+const AFIRMACION: char = '=';
+const NEGACION: char = '!';
+const PROPIEDAD: char = 'p';
+const NO_PROPIEDAD: char = 'P';
+const IGNORAR_MAYUSCULAS: char = 'i';
+const QUITAR_MODIFICADOR: char = '-';
+const PUNTO_INCLUYE_SALTOS: char = 's';
+const ANCLAS_POR_LINEA: char = 'm';
+const ESCAPE_HEXADECIMAL: char = 'x';
+const ESCAPE_UNICODE: char = 'u';
+
+///Obtiene la clase que corresponde a una barra seguida del caracter indicado, si es una
+///clase abreviada: \d (dígitos), \w (caracteres de palabra) o \s (espacios),
+///y sus negaciones en mayúscula \D, \W y \S.
+///Las clases de dígitos solo existen en el dialecto de Perl.
+fn obtener_clase_abreviada(c: char, dialecto: Dialecto) -> Option<ClaseChar> {
+    match c {
+        'd' if dialecto == Dialecto::Perl => Some(ClaseChar::Digit(false)),
+        'D' if dialecto == Dialecto::Perl => Some(ClaseChar::Digit(true)),
+        'w' => Some(ClaseChar::Palabra(false)),
+        'W' => Some(ClaseChar::Palabra(true)),
+        's' => Some(ClaseChar::Space(false)),
+        'S' => Some(ClaseChar::Space(true)),
+        _ => None,
+    }
+}
+
+///Obtiene la clase de caracteres que corresponde al nombre indicado dentro de [: y :],
+///si es una de las clases POSIX que soporta nuestro grep rústico.
+fn obtener_clase_por_nombre(nombre: &str) -> Option<ClaseChar> {
+    match nombre {
+        "alpha" => Some(ClaseChar::Alpha(false)),
+        "alnum" => Some(ClaseChar::Alnum(false)),
+        "digit" => Some(ClaseChar::Digit(false)),
+        "lower" => Some(ClaseChar::Lower(false)),
+        "upper" => Some(ClaseChar::Upper(false)),
+        "space" => Some(ClaseChar::Space(false)),
+        "punct" => Some(ClaseChar::Punct(false)),
+        "xdigit" => Some(ClaseChar::Xdigit(false)),
+        "cntrl" => Some(ClaseChar::Cntrl(false)),
+        "graph" => Some(ClaseChar::Graph(false)),
+        "print" => Some(ClaseChar::Print(false)),
+        "blank" => Some(ClaseChar::Blank(false)),
+        _ => None,
+    }
+}
+
+///Interpreta un caracter de una expresión regular extendida (ERE) o de Perl,
+///donde los operadores se escriben sin barra.
+fn leer_simbolo_extendido(c: char) -> Simbolo {
+    match c {
+        PUNTO | CORCHETE_ABIERTO | BARRA | PARENTESIS_ABIERTO | PARENTESIS_CERRADO | FUNCION_OR
+        | LLAVE_ABIERTA | INTERROGACION | ASTERISCO | MAS | CARET | DOLAR => Simbolo::Operador(c),
+        _ => Simbolo::Literal(c),
+    }
+}
+
+///Indica si una barra seguida del caracter forma una secuencia de escape, como \t o \x41.
+fn es_escape(c: char) -> bool {
+    matches!(
+        c,
+        't' | 'n' | 'r' | 'f' | 'v' | '0' | ESCAPE_HEXADECIMAL | ESCAPE_UNICODE
+    )
+}
+
+///Construye un nodo que ocupa la parte de la expresión entre las posiciones indicadas.
+fn nodo(tipo: TipoDeNodo, inicio: usize, fin: usize) -> NodoAst {
+    NodoAst { tipo, inicio, fin }
+}
+
+///Analiza una expresión regular y construye su árbol de sintaxis, que luego se traduce
+///a los pasos que se evalúan. Recorre la expresión una sola vez, de izquierda a derecha,
+///y guarda el nombre de cada grupo de captura a medida que se abren.
+///Cada error indica la posición en bytes de la parte de la expresión que lo provocó.
+pub(crate) struct Analizador<'a> {
+    expresion: &'a str,
+    chars_iter: Chars<'a>,
+    dialecto: Dialecto,
+    maximo_repeticiones: usize,
+    nombres_capturas: Vec<Option<String>>,
+}
+
+impl<'a> Analizador<'a> {
+    ///Crea un analizador para la expresión, con el dialecto y el máximo de repeticiones
+    ///indicados en la configuración. La posición 0 de los nombres de captura corresponde
+    ///a la coincidencia completa.
+    pub(crate) fn new(expresion: &'a str, configuracion: &Configuracion) -> Self {
+        Analizador {
+            expresion,
+            chars_iter: expresion.chars(),
+            dialecto: configuracion.dialecto,
+            maximo_repeticiones: configuracion.maximo_repeticiones,
+            nombres_capturas: vec![None],
+        }
+    }
+
+    ///Analiza la expresión completa y devuelve las alternativas separadas por la función OR,
+    ///cada una con sus nodos.
+    ///Antes se verifica que la expresión esté escrita correctamente.
+    pub(crate) fn analizar(&mut self) -> Result<Vec<Vec<NodoAst>>, ErrorEnExpresion> {
+        self.verificar_escritura()?;
+        self.analizar_alternativas(None)
+    }
+
+    ///Devuelve el nombre de cada grupo de captura encontrado al analizar la expresión,
+    ///en el orden en que se abren, o None si el grupo no tiene nombre.
+    pub(crate) fn nombres_capturas(self) -> Vec<Option<String>> {
+        self.nombres_capturas
+    }
+
+    ///Devuelve la posición en bytes de la expresión hasta la que se leyó.
+    fn posicion(&self) -> usize {
+        self.expresion.len() - self.chars_iter.as_str().len()
+    }
+
+    ///Construye un error para la parte de la expresión entre las posiciones indicadas.
+    fn error(&self, tipo: Error, inicio: usize, fin: usize) -> ErrorEnExpresion {
+        ErrorEnExpresion::new(tipo, self.expresion, inicio, fin)
+    }
+
+    ///Construye un error para el caracter que comienza en la posición indicada,
+    ///o para el final de la expresión si no hay ningún caracter ahí.
+    fn error_en_caracter(&self, tipo: Error, inicio: usize) -> ErrorEnExpresion {
+        let largo = self.expresion[inicio..]
+            .chars()
+            .next()
+            .map_or(0, char::len_utf8);
+        self.error(tipo, inicio, inicio + largo)
+    }
+
+    ///Verifica si la expresión regular está escrita correctamente.
+    ///Determina si las llaves y los corchetes se abren y se cierran
+    ///como corresponde. En cada caso devuelve un error explicativo.
+    ///El contenido de los corchetes se recorre con sus propias reglas,
+    ///por lo que un ] o una llave dentro de ellos no se tienen en cuenta.
+    ///Además, verifica que la función OR no esté dentro de llaves.
+    ///Los caracteres escapados con una barra no se tienen en cuenta, salvo en el dialecto
+    ///básico, donde las llaves y la función OR se escriben con barra.
+    ///En el dialecto de Perl, las llaves de \x{...}, \u{...}, \p{...} y \P{...} tampoco se
+    ///tienen en cuenta: si no se cierran, el error lo devuelve el análisis de esa secuencia.
+    ///Al terminar, se vuelve al comienzo de la expresión.
+    fn verificar_escritura(&mut self) -> Result<(), ErrorEnExpresion> {
+        let mut llaves_abiertas: Vec<usize> = Vec::new();
+        let es_basico = self.dialecto == Dialecto::Basico;
+        let es_perl = self.dialecto == Dialecto::Perl;
+        loop {
+            let inicio = self.posicion();
+            let (c, es_operador) = match self.chars_iter.next() {
+                None => break,
+                Some(BARRA) => match self.chars_iter.next() {
+                    Some(ESCAPE_HEXADECIMAL | ESCAPE_UNICODE | PROPIEDAD | NO_PROPIEDAD)
+                        if es_perl && self.chars_iter.as_str().starts_with(LLAVE_ABIERTA) =>
+                    {
+                        let resto = self.chars_iter.as_str();
+                        let fin = resto.find(LLAVE_CERRADA).map_or(resto.len(), |fin| fin + 1);
+                        self.chars_iter = resto[fin..].chars();
+                        continue;
+                    }
+                    Some(escapado) => (escapado, es_basico),
+                    None => continue,
+                },
+                Some(CORCHETE_ABIERTO) => {
+                    self.conseguir_lista()?;
+                    continue;
+                }
+                Some(CORCHETE_CERRADO) => {
+                    return Err(self.error_en_caracter(Error::ErrorEnCorchetes, inicio));
+                }
+                Some(c) => (c, !es_basico),
+            };
+            match c {
+                LLAVE_ABIERTA if es_operador => llaves_abiertas.push(inicio),
+                LLAVE_CERRADA if es_operador => {
+                    llaves_abiertas
+                        .pop()
+                        .ok_or_else(|| self.error(Error::ErrorEnLlaves, inicio, self.posicion()))?;
+                }
+                FUNCION_OR if es_operador && !llaves_abiertas.is_empty() => {
+                    return Err(self.error(Error::ErrorEnFuncionOR, inicio, self.posicion()));
+                }
+                _ => {}
+            }
+        }
+        if let Some(&inicio) = llaves_abiertas.last() {
+            return Err(self.error_en_caracter(Error::ErrorEnLlaves, inicio));
+        }
+        self.chars_iter = self.expresion.chars();
+        Ok(())
+    }
+
+    ///Obtiene el caracter que representa una barra seguida del caracter indicado, si es una
+    ///secuencia de escape: \t, \n, \r, \f, \v y \0 para los caracteres de control, y \xHH,
+    ///\x{HHHH} y \u{HHHH} para el caracter con ese código en hexadecimal.
+    ///El inicio es la posición de la barra.
+    /// - Si el código no es válido, se devuelve un error en el escape.
+    fn obtener_escape(&mut self, c: char, inicio: usize) -> Result<Option<char>, ErrorEnExpresion> {
+        let caracter = match c {
+            't' => '\t',
+            'n' => '\n',
+            'r' => '\r',
+            'f' => '\u{0C}',
+            'v' => '\u{0B}',
+            '0' => '\0',
+            ESCAPE_HEXADECIMAL | ESCAPE_UNICODE => self.obtener_codigo_hexadecimal(c, inicio)?,
+            _ => return Ok(None),
+        };
+        Ok(Some(caracter))
+    }
+
+    ///Obtiene el caracter cuyo código en hexadecimal sigue a \x o \u.
+    ///El código va entre llaves o, solo luego de \x, son exactamente dos dígitos.
+    /// - Si la llave no se cierra, se devuelve un error que señala desde la barra hasta el final
+    ///   de la expresión.
+    /// - Si no son dígitos hexadecimales o el código no corresponde a un caracter, se devuelve
+    ///   un error que señala toda la secuencia de escape.
+    fn obtener_codigo_hexadecimal(
+        &mut self,
+        c: char,
+        inicio: usize,
+    ) -> Result<char, ErrorEnExpresion> {
+        let resto = self.chars_iter.as_str();
+        let (codigo, siguiente, con_llaves) = match resto.strip_prefix(LLAVE_ABIERTA) {
+            Some(contenido) => match contenido.find(LLAVE_CERRADA) {
+                Some(fin) => (&contenido[..fin], &contenido[fin + 1..], true),
+                None => return Err(self.error(Error::ErrorEnEscape, inicio, self.expresion.len())),
+            },
+            None if c == ESCAPE_HEXADECIMAL => {
+                let fin = resto.char_indices().nth(2).map_or(resto.len(), |(i, _)| i);
+                (&resto[..fin], &resto[fin..], false)
+            }
+            None => return Err(self.error(Error::ErrorEnEscape, inicio, self.posicion())),
+        };
+        let fin = self.expresion.len() - siguiente.len();
+
+        let es_valido = (con_llaves || codigo.chars().count() == 2)
+            && !codigo.is_empty()
+            && codigo.chars().all(|c| c.is_ascii_hexdigit());
+        match u32::from_str_radix(codigo, 16)
+            .ok()
+            .and_then(char::from_u32)
+        {
+            Some(caracter) if es_valido => {
+                self.chars_iter = siguiente.chars();
+                Ok(caracter)
+            }
+            _ => Err(self.error(Error::ErrorEnEscape, inicio, fin)),
+        }
+    }
+
+    ///Obtiene la clase de una propiedad de Unicode, luego de haber leído \p (o \P si es negada).
+    ///El nombre de la propiedad va entre llaves, como en \p{Lu} o \p{Greek}, o es una única letra,
+    ///como en \pL, y puede ser una categoría general o una escritura de Scripts.txt.
+    ///El inicio es la posición de la barra.
+    /// - Si la llave no se cierra o la propiedad no es una de las soportadas, se devuelve un error en la clase.
+    fn obtener_clase_de_propiedad(
+        &mut self,
+        negada: bool,
+        inicio: usize,
+    ) -> Result<ClaseChar, ErrorEnExpresion> {
+        let resto = self.chars_iter.as_str();
+        let nombre = match resto.strip_prefix(LLAVE_ABIERTA) {
+            Some(contenido) => {
+                let fin = contenido
+                    .find(LLAVE_CERRADA)
+                    .ok_or_else(|| self.error(Error::ErrorEnClase, inicio, self.expresion.len()))?;
+                self.chars_iter = contenido[fin + 1..].chars();
+                &contenido[..fin]
+            }
+            None => {
+                let letra = self
+                    .chars_iter
+                    .next()
+                    .ok_or_else(|| self.error(Error::ErrorEnClase, inicio, self.posicion()))?;
+                &resto[..letra.len_utf8()]
+            }
+        };
+
+        if let Some(categoria) = CATEGORIAS.iter().find(|categoria| **categoria == nombre) {
+            return Ok(ClaseChar::Categoria(categoria, negada));
+        }
+        match ESCRITURAS.iter().find(|escritura| **escritura == nombre) {
+            Some(escritura) => Ok(ClaseChar::Escritura(escritura, negada)),
+            None => Err(self.error(Error::ErrorEnClase, inicio, self.posicion())),
+        }
+    }
+
+    ///Obtiene una clase de caracteres con nombre ([:nombre:]) dentro de un corchete,
+    ///luego de haber leído el corchete que la abre, que está en la posición inicio.
+    /// - Si la clase no se cierra con :], se devuelve un error en los corchetes.
+    /// - Si el nombre no es el de una de las clases predefinidas, se devuelve un error en la clase.
+    fn obtener_clase_con_nombre(&mut self, inicio: usize) -> Result<ClaseChar, ErrorEnExpresion> {
+        self.chars_iter.next();
+        let resto = self.chars_iter.as_str();
+        let fin_nombre = resto
+            .find(FIN_CLASE_CON_NOMBRE)
+            .ok_or_else(|| self.error(Error::ErrorEnCorchetes, inicio, self.expresion.len()))?;
+        let nombre = &resto[..fin_nombre];
+        self.chars_iter = resto[fin_nombre + FIN_CLASE_CON_NOMBRE.len()..].chars();
+
+        obtener_clase_por_nombre(nombre)
+            .ok_or_else(|| self.error(Error::ErrorEnClase, inicio, self.posicion()))
+    }
+
+    ///Agrega al contenido de un corchete el caracter leído, que comienza en la posición inicio,
+    ///o el rango que comienza en él si le sigue un guión que no es el último caracter del corchete.
+    ///Salvo en el dialecto básico, el extremo final del rango puede estar escapado con una barra,
+    ///y en el de Perl puede ser una secuencia de escape como \x7F.
+    ///Los literales y los rangos se guardan por separado, y los rangos sin expandir.
+    /// - Si el rango está invertido (por ejemplo z-a), se devuelve un error en los corchetes.
+    fn agregar_literal_o_rango(
+        &mut self,
+        c: char,
+        inicio: usize,
+        literales: &mut HashSet<char>,
+        rangos: &mut Vec<(char, char)>,
+    ) -> Result<(), ErrorEnExpresion> {
+        let antes_del_guion = self.chars_iter.clone();
+        if self.chars_iter.next() != Some(SEPARADOR_RANGO) {
+            self.chars_iter = antes_del_guion;
+            literales.insert(c);
+            return Ok(());
+        }
+
+        let inicio_fin = self.posicion();
+        let fin = match self.chars_iter.next() {
+            None | Some(CORCHETE_CERRADO) => {
+                self.chars_iter = antes_del_guion;
+                literales.insert(c);
+                return Ok(());
+            }
+            Some(BARRA) if self.dialecto != Dialecto::Basico => {
+                let escapado = self
+                    .chars_iter
+                    .next()
+                    .ok_or_else(|| self.error_en_caracter(Error::ErrorEnCorchetes, inicio_fin))?;
+                match self.dialecto {
+                    Dialecto::Perl => self
+                        .obtener_escape(escapado, inicio_fin)?
+                        .unwrap_or(escapado),
+                    _ => escapado,
+                }
+            }
+            Some(fin) => fin,
+        };
+        if fin < c {
+            return Err(self.error(Error::ErrorEnCorchetes, inicio, self.posicion()));
+        }
+        rangos.push((c, fin));
+        Ok(())
+    }
+
+    ///Obtiene la clase de caracter que se debe evaluar a partir del contenido de un corchete,
+    ///luego de haber leído el corchete que lo abre.
+    ///El contenido puede combinar literales, rangos, clases con nombre ([:alpha:]), clases
+    ///abreviadas (\d) y propiedades de Unicode (\p{L}), y la negación se aplica sobre el conjunto completo.
+    ///Se siguen las reglas de POSIX para los casos particulares:
+    /// - El ^ solo niega el corchete si es el primer caracter; en otra posición es un literal.
+    /// - Un ] al comienzo (luego del ^, si lo hay) es un literal y no cierra el corchete.
+    /// - Un guión al comienzo o al final es un literal.
+    /// - Una barra seguida de un caracter que no es una clase abreviada escapa ese caracter,
+    ///   salvo que forme una secuencia de escape como \t o \x{41}. Las clases abreviadas, las
+    ///   propiedades y las secuencias de escape solo existen en el dialecto de Perl, y en el
+    ///   básico la barra es un literal, como indica POSIX.
+    ///
+    ///Si solo hay literales y rangos, se devuelve una clase simple; si hay clases, se devuelve
+    ///la unión de esas clases con los literales y rangos.
+    ///Si el corchete no se cierra, se devuelve un error que señala el corchete que lo abre.
+    fn conseguir_lista(&mut self) -> Result<ClaseChar, ErrorEnExpresion> {
+        let apertura = self.posicion() - CORCHETE_ABIERTO.len_utf8();
+        let es_negado = self.chars_iter.as_str().starts_with(CARET);
+        if es_negado {
+            self.chars_iter.next();
+        }
+
+        let mut clases: Vec<ClaseChar> = Vec::new();
+        let mut literales: HashSet<char> = HashSet::new();
+        let mut rangos: Vec<(char, char)> = Vec::new();
+        let mut es_primero = true;
+
+        loop {
+            let inicio = self.posicion();
+            let c = self
+                .chars_iter
+                .next()
+                .ok_or_else(|| self.error_en_caracter(Error::ErrorEnCorchetes, apertura))?;
+            match c {
+                CORCHETE_CERRADO if !es_primero => break,
+                CORCHETE_ABIERTO if self.chars_iter.as_str().starts_with(INDICADOR_CLASE) => {
+                    clases.push(self.obtener_clase_con_nombre(inicio)?);
+                }
+                BARRA if self.dialecto == Dialecto::Perl => {
+                    let escapado = self
+                        .chars_iter
+                        .next()
+                        .ok_or_else(|| self.error_en_caracter(Error::ErrorEnCorchetes, apertura))?;
+                    match obtener_clase_abreviada(escapado, self.dialecto) {
+                        Some(clase) => clases.push(clase),
+                        None if escapado == PROPIEDAD || escapado == NO_PROPIEDAD => {
+                            clases.push(
+                                self.obtener_clase_de_propiedad(escapado == NO_PROPIEDAD, inicio)?,
+                            );
+                        }
+                        None => {
+                            let literal =
+                                self.obtener_escape(escapado, inicio)?.unwrap_or(escapado);
+                            self.agregar_literal_o_rango(
+                                literal,
+                                inicio,
+                                &mut literales,
+                                &mut rangos,
+                            )?
+                        }
+                    }
+                }
+                BARRA if self.dialecto == Dialecto::Extendido => {
+                    let escapado = self
+                        .chars_iter
+                        .next()
+                        .ok_or_else(|| self.error_en_caracter(Error::ErrorEnCorchetes, apertura))?;
+                    self.agregar_literal_o_rango(escapado, inicio, &mut literales, &mut rangos)?
+                }
+                c => self.agregar_literal_o_rango(c, inicio, &mut literales, &mut rangos)?,
+            }
+            es_primero = false;
+        }
+
+        if clases.is_empty() {
+            return Ok(ClaseChar::Simple(literales, rangos, es_negado));
+        }
+        if !literales.is_empty() || !rangos.is_empty() {
+            clases.push(ClaseChar::Simple(literales, rangos, false));
+        }
+        Ok(ClaseChar::Compuesta(clases, es_negado))
+    }
+
+    ///Obtiene la cantidad de repeticiones escrita de un lado de la coma dentro de las llaves,
+    ///que comienza en la posición inicio de la expresión.
+    ///Se ignoran los espacios alrededor del número y, si no hay número, se devuelve None.
+    /// - Si hay algo que no es un dígito, se devuelve un error que señala ese caracter.
+    /// - Si la cantidad supera el máximo configurado, se devuelve un error que señala el número.
+    fn obtener_cantidad_de_llave(
+        &self,
+        texto: &str,
+        inicio: usize,
+    ) -> Result<Option<usize>, ErrorEnExpresion> {
+        let recortado = texto.trim();
+        if recortado.is_empty() {
+            return Ok(None);
+        }
+        let inicio = inicio + texto.len() - texto.trim_start().len();
+        if let Some((i, _)) = recortado.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+            return Err(self.error_en_caracter(Error::LlavesConContenidoInvalido, inicio + i));
+        }
+        match recortado.parse::<usize>() {
+            Ok(cantidad) if cantidad <= self.maximo_repeticiones => Ok(Some(cantidad)),
+            _ => Err(self.error(
+                Error::RepeticionesExcedidas,
+                inicio,
+                inicio + recortado.len(),
+            )),
+        }
+    }
+
+    ///Obtiene la repetición indicada entre llaves, luego de haber leído la llave que la abre,
+    ///que está en la posición inicio. Puede ser {n} (exactamente n veces), {n,} (al menos n),
+    ///{,m} (a lo sumo m) o {n,m} (entre n y m).
+    ///En el dialecto básico las llaves se escriben con barra, como en \{n,m\}.
+    /// - Si las llaves no se cierran, se devuelve un error que señala la que se abre.
+    /// - Si las llaves no indican ninguna cantidad, como en {} o {,}, se devuelve un error.
+    /// - Si el contenido no son números separados por una coma, como en {a}, se devuelve un error.
+    /// - Si el mínimo es mayor que el máximo o alguna cantidad supera el máximo configurado,
+    ///   se devuelve un error.
+    fn obtener_repeticion_de_llave(
+        &mut self,
+        inicio: usize,
+    ) -> Result<Repeticion, ErrorEnExpresion> {
+        let resto = self.chars_iter.as_str();
+        let (fin, largo_cierre) = match self.dialecto {
+            Dialecto::Basico => (resto.find(LLAVE_CERRADA_BASICA), LLAVE_CERRADA_BASICA.len()),
+            _ => (resto.find(LLAVE_CERRADA), LLAVE_CERRADA.len_utf8()),
+        };
+        let fin = fin.ok_or_else(|| self.error(Error::ErrorEnLlaves, inicio, self.posicion()))?;
+        let inicio_contenido = self.posicion();
+        let contenido = &resto[..fin];
+        self.chars_iter = resto[fin + largo_cierre..].chars();
+        let fin_llaves = self.posicion();
+
+        match contenido.split_once(SEPARADOR_LLAVE) {
+            None => match self.obtener_cantidad_de_llave(contenido, inicio_contenido)? {
+                Some(cantidad) => Ok(Repeticion::Exacta(cantidad)),
+                None => Err(self.error(Error::LlavesVacias, inicio, fin_llaves)),
+            },
+            Some((texto_min, texto_max)) => {
+                let inicio_max = inicio_contenido + texto_min.len() + SEPARADOR_LLAVE.len_utf8();
+                let min = self.obtener_cantidad_de_llave(texto_min, inicio_contenido)?;
+                let max = self.obtener_cantidad_de_llave(texto_max, inicio_max)?;
+                match (min, max) {
+                    (None, None) => Err(self.error(Error::LlavesVacias, inicio, fin_llaves)),
+                    (Some(min), Some(max)) if min > max => Err(self.error(
+                        Error::RangoDeLlavesInvertido,
+                        inicio_contenido,
+                        inicio_contenido + contenido.len(),
+                    )),
+                    (min, max) => Ok(Repeticion::Rango { min, max }),
+                }
+            }
+        }
+    }
+
+    ///Aplica la repetición indicada por el caracter, que comienza en la posición inicio,
+    ///al nodo anterior, y luego el modificador que le pueda seguir, en el dialecto de Perl:
+    /// - Si sigue un signo de interrogación, la repetición es perezosa.
+    /// - Si sigue un signo de más, la repetición es posesiva.
+    ///
+    ///En los otros dialectos no hay modificadores, y una repetición que sigue a otra se aplica
+    ///sobre la anterior, como si estuviera entre paréntesis.
+    /// - Si no hay un nodo anterior al que aplicarle la repetición (porque la expresión,
+    ///   la alternativa o el grupo comienza con ella), se devuelve un error.
+    /// - En el dialecto de Perl, si la repetición sigue a otra (como en a** o a{2}{3}),
+    ///   se devuelve un error, ya que solo un ? o un + pueden seguirla como modificador.
+    fn aplicar_repeticion(
+        &mut self,
+        c: char,
+        inicio: usize,
+        nodos: &mut Vec<NodoAst>,
+    ) -> Result<(), ErrorEnExpresion> {
+        let anterior = match nodos.pop() {
+            Some(anterior) if !matches!(anterior.tipo, TipoDeNodo::Modificadores(_)) => anterior,
+            _ => return Err(self.error(Error::ErrorEnRepeticion, inicio, self.posicion())),
+        };
+        if self.dialecto == Dialecto::Perl && matches!(anterior.tipo, TipoDeNodo::Repeticion { .. })
+        {
+            return Err(self.error(Error::ErrorEnRepeticion, inicio, self.posicion()));
+        }
+        let mut repeticiones = match c {
+            LLAVE_ABIERTA => self.obtener_repeticion_de_llave(inicio)?,
+            INTERROGACION => Repeticion::Rango {
+                min: Some(0),
+                max: Some(1),
+            },
+            ASTERISCO => Repeticion::Alguna,
+            _ => Repeticion::Rango {
+                min: Some(1),
+                max: None,
+            },
+        };
+
+        if self.dialecto == Dialecto::Perl {
+            if self.chars_iter.as_str().starts_with(INTERROGACION) {
+                self.chars_iter.next();
+                repeticiones = repeticiones.perezosa();
+            } else if self.chars_iter.as_str().starts_with(MAS) {
+                self.chars_iter.next();
+                repeticiones = repeticiones.posesiva();
+            }
+        }
+        let inicio = anterior.inicio;
+        let tipo = TipoDeNodo::Repeticion {
+            nodo: Box::new(anterior),
+            repeticiones,
+        };
+        nodos.push(nodo(tipo, inicio, self.posicion()));
+        Ok(())
+    }
+
+    ///Analiza un caracter escapado con una barra, que está en la posición inicio.
+    /// - Si es un dígito del 1 al 9, es una referencia a lo capturado por ese grupo,
+    ///   que debe haberse abierto antes en la expresión.
+    /// - Si es una clase abreviada (\d, \w, \s o sus negaciones), es una serie de esa clase.
+    /// - Si es una p o una P, es una serie de la propiedad de Unicode indicada o de su negación.
+    /// - Si es una b o una B, es un límite de palabra o su negación.
+    /// - Si es un < o un >, es el inicio o el fin de una palabra.
+    /// - Si es una secuencia de escape (\t, \n, \r, \f, \v, \0, \xHH, \x{HHHH} o \u{HHHH}),
+    ///   es el literal que representa.
+    /// - En otro caso, es el literal que sigue a la barra.
+    ///
+    ///Las propiedades, las secuencias de escape y las clases \d y \D solo existen en el dialecto
+    ///de Perl; en los otros, la barra seguida de esos caracteres es el literal que sigue.
+    fn analizar_barra(&mut self, inicio: usize) -> Result<NodoAst, ErrorEnExpresion> {
+        let c = self
+            .chars_iter
+            .next()
+            .ok_or_else(|| self.error_en_caracter(Error::CaracterNoProcesable, inicio))?;
+        let es_perl = self.dialecto == Dialecto::Perl;
+        let caracter = if es_perl && (c == PROPIEDAD || c == NO_PROPIEDAD) {
+            Caracter::Serie(self.obtener_clase_de_propiedad(c == NO_PROPIEDAD, inicio)?)
+        } else if let Some(clase) = obtener_clase_abreviada(c, self.dialecto) {
+            Caracter::Serie(clase)
+        } else if es_perl && es_escape(c) {
+            Caracter::Literal(self.obtener_escape(c, inicio)?.unwrap_or(c))
+        } else {
+            match c {
+                digito @ '1'..='9' => {
+                    let captura = digito as usize - '0' as usize;
+                    if captura >= self.nombres_capturas.len() {
+                        return Err(self.error(Error::ErrorEnReferencia, inicio, self.posicion()));
+                    }
+                    Caracter::Referencia(captura)
+                }
+                LIMITE_PALABRA => Caracter::LimitePalabra,
+                NO_LIMITE_PALABRA => Caracter::NoLimitePalabra,
+                INICIO_PALABRA => Caracter::InicioPalabra,
+                FIN_PALABRA => Caracter::FinPalabra,
+                literal => Caracter::Literal(literal),
+            }
+        };
+        Ok(nodo(
+            TipoDeNodo::Caracter(caracter),
+            inicio,
+            self.posicion(),
+        ))
+    }
+
+    ///Obtiene el nombre de un grupo de captura, hasta el símbolo > que lo cierra.
+    ///El nombre debe estar formado por letras, dígitos o guiones bajos y no comenzar con un dígito.
+    /// - Si no lo está, se devuelve un error que señala el nombre hasta el caracter inválido.
+    fn obtener_nombre_de_grupo(&mut self) -> Result<String, ErrorEnExpresion> {
+        let inicio = self.posicion();
+        let mut nombre = String::new();
+        for c in self.chars_iter.by_ref() {
+            match c {
+                NOMBRE_CERRADO if !nombre.is_empty() => return Ok(nombre),
+                'a'..='z' | 'A'..='Z' | '_' => nombre.push(c),
+                '0'..='9' if !nombre.is_empty() => nombre.push(c),
+                _ => break,
+            }
+        }
+        Err(self.error(Error::ErrorEnNombreDeGrupo, inicio, self.posicion()))
+    }
+
+    ///Determina el tipo de grupo que se abre, según lo que sigue al paréntesis,
+    ///y el número de captura si el grupo es de captura.
+    /// - Si sigue ?: el grupo no es de captura.
+    /// - Si sigue ?> el grupo es atómico (y no es de captura).
+    /// - Si sigue ?= o ?! el grupo es una anticipación positiva o negativa.
+    /// - Si sigue ?<= o ?<! el grupo es una retrospección positiva o negativa.
+    /// - Si sigue ?P<nombre> o ?<nombre> el grupo es de captura y tiene nombre.
+    /// - En otro caso el grupo es de captura y no tiene nombre.
+    ///
+    ///Los grupos de captura reciben el número siguiente a los grupos ya abiertos.
+    ///Los grupos que comienzan con ? solo existen en el dialecto de Perl.
+    /// - Si lo que sigue al ? no es ninguno de los anteriores, se devuelve un error que
+    ///   señala el comienzo del grupo, que está en la posición inicio.
+    fn obtener_tipo_de_grupo(
+        &mut self,
+        inicio: usize,
+    ) -> Result<(TipoDeGrupo, Option<usize>), ErrorEnExpresion> {
+        let resto = self.chars_iter.as_str();
+        if self.dialecto != Dialecto::Perl || !resto.starts_with(INTERROGACION) {
+            self.nombres_capturas.push(None);
+            return Ok((TipoDeGrupo::Comun, Some(self.nombres_capturas.len() - 1)));
+        }
+        self.chars_iter.next();
+        let inicio_nombre = self.posicion();
+        let nombre = match self.chars_iter.next() {
+            Some(SIN_CAPTURA) => return Ok((TipoDeGrupo::Comun, None)),
+            Some(ATOMICO) => return Ok((TipoDeGrupo::Atomico, None)),
+            Some(AFIRMACION) => return Ok((TipoDeGrupo::Anticipacion { negada: false }, None)),
+            Some(NEGACION) => return Ok((TipoDeGrupo::Anticipacion { negada: true }, None)),
+            Some(NOMBRE_ABIERTO) if self.chars_iter.as_str().starts_with(AFIRMACION) => {
+                self.chars_iter.next();
+                return Ok((TipoDeGrupo::Retrospeccion { negada: false }, None));
+            }
+            Some(NOMBRE_ABIERTO) if self.chars_iter.as_str().starts_with(NEGACION) => {
+                self.chars_iter.next();
+                return Ok((TipoDeGrupo::Retrospeccion { negada: true }, None));
+            }
+            Some(INDICADOR_NOMBRE) if self.chars_iter.as_str().starts_with(NOMBRE_ABIERTO) => {
+                self.chars_iter.next();
+                self.obtener_nombre_de_grupo()?
+            }
+            Some(NOMBRE_ABIERTO) => self.obtener_nombre_de_grupo()?,
+            _ => return Err(self.error(Error::ErrorEnParentesis, inicio, self.posicion())),
+        };
+        if self.nombres_capturas.contains(&Some(nombre.clone())) {
+            return Err(self.error(Error::ErrorEnNombreDeGrupo, inicio_nombre, self.posicion()));
+        }
+        self.nombres_capturas.push(Some(nombre));
+        Ok((TipoDeGrupo::Comun, Some(self.nombres_capturas.len() - 1)))
+    }
+
+    ///Obtiene los modificadores que siguen a un paréntesis abierto, si los hay.
+    /// - La i indica que se ignoran las mayúsculas.
+    /// - La s indica que el punto también coincide con un salto de línea.
+    /// - La m indica que el ^ y el $ se cumplen al inicio y al final de cada línea.
+    ///
+    ///Los modificadores que siguen a un guión se desactivan, como en (?i-s).
+    ///Devuelve los modificadores y si abren un grupo sin captura, como en (?i:...).
+    ///Si no hay modificadores, no se consume ningún caracter.
+    fn obtener_modificadores(&mut self) -> Option<(Modificadores, bool)> {
+        let mut siguientes = self.chars_iter.clone();
+        if siguientes.next() != Some(INTERROGACION) {
+            return None;
+        }
+        let mut modificadores = Modificadores::default();
+        let mut activar = true;
+        let mut hay_modificadores = false;
+        loop {
+            let modificador = match siguientes.next() {
+                Some(QUITAR_MODIFICADOR) if activar => {
+                    activar = false;
+                    continue;
+                }
+                Some(IGNORAR_MAYUSCULAS) => &mut modificadores.ignorar_mayusculas,
+                Some(PUNTO_INCLUYE_SALTOS) => &mut modificadores.punto_incluye_saltos,
+                Some(ANCLAS_POR_LINEA) => &mut modificadores.anclas_por_linea,
+                Some(PARENTESIS_CERRADO) if hay_modificadores => {
+                    self.chars_iter = siguientes;
+                    return Some((modificadores, false));
+                }
+                Some(SIN_CAPTURA) if hay_modificadores => {
+                    self.chars_iter = siguientes;
+                    return Some((modificadores, true));
+                }
+                _ => return None,
+            };
+            *modificador = Some(activar);
+            hay_modificadores = true;
+        }
+    }
+
+    ///Analiza un paréntesis abierto, que está en la posición inicio, con las alternativas
+    ///que aparecen hasta el paréntesis que lo cierra.
+    ///Si el paréntesis es un modificador como (?i) o (?s), el nodo es ese modificador, que se
+    ///aplica hasta el final del grupo en el que aparece. Si el modificador abre un grupo,
+    ///como (?i:...), se aplica solo dentro de ese grupo.
+    ///Los modificadores solo existen en el dialecto de Perl.
+    fn analizar_parentesis(&mut self, inicio: usize) -> Result<NodoAst, ErrorEnExpresion> {
+        let modificadores = match self.dialecto {
+            Dialecto::Perl => self.obtener_modificadores(),
+            _ => None,
+        };
+        let (tipo, captura, modificadores) = match modificadores {
+            Some((modificadores, false)) => {
+                let tipo = TipoDeNodo::Modificadores(modificadores);
+                return Ok(nodo(tipo, inicio, self.posicion()));
+            }
+            Some((modificadores, true)) => (TipoDeGrupo::Comun, None, modificadores),
+            None => {
+                let (tipo, captura) = self.obtener_tipo_de_grupo(inicio)?;
+                (tipo, captura, Modificadores::default())
+            }
+        };
+        let alternativas = self.analizar_alternativas(Some(inicio))?;
+        let tipo = TipoDeNodo::Grupo {
+            alternativas,
+            captura,
+            tipo,
+            modificadores,
+        };
+        Ok(nodo(tipo, inicio, self.posicion()))
+    }
+
+    ///Indica si una expresión regular básica termina en la posición actual, o si termina
+    ///la alternativa o el grupo en el que se está, para saber si un $ es un ancla.
+    fn es_fin_de_alternativa_basica(&self) -> bool {
+        let resto = self.chars_iter.as_str();
+        resto.is_empty()
+            || resto.starts_with(PARENTESIS_CERRADO_BASICO)
+            || resto.starts_with(FUNCION_OR_BASICA)
+    }
+
+    ///Interpreta un caracter de una expresión regular básica (BRE), que es la primera de
+    ///las interpretaciones que tiene cada dialecto.
+    /// - Una barra seguida de {, (, ), |, + o ? es ese operador, y sin la barra son literales.
+    /// - El * es un literal si está al comienzo de una alternativa o de un grupo, o si solo
+    ///   lo precede el ^ del comienzo, como en ^*a.
+    /// - El ^ es un ancla solo al comienzo, y el $ solo al final, de una alternativa o de un grupo.
+    /// - El punto, el corchete abierto y la barra seguida de otro caracter son operadores.
+    ///
+    ///Para saber si el caracter está al comienzo se reciben los nodos anteriores de la alternativa.
+    fn leer_simbolo_basico(&mut self, c: char, anteriores: &[NodoAst]) -> Simbolo {
+        let es_primero = anteriores.is_empty();
+        let solo_caret = matches!(
+            anteriores,
+            [NodoAst {
+                tipo: TipoDeNodo::Caret,
+                ..
+            }]
+        );
+        match c {
+            BARRA => match self.chars_iter.as_str().chars().next() {
+                Some(
+                    operador @ (LLAVE_ABIERTA | PARENTESIS_ABIERTO | PARENTESIS_CERRADO
+                    | FUNCION_OR | MAS | INTERROGACION),
+                ) => {
+                    self.chars_iter.next();
+                    Simbolo::Operador(operador)
+                }
+                _ => Simbolo::Operador(BARRA),
+            },
+            ASTERISCO if es_primero || solo_caret => Simbolo::Literal(c),
+            CARET if !es_primero => Simbolo::Literal(c),
+            DOLAR if !self.es_fin_de_alternativa_basica() => Simbolo::Literal(c),
+            PUNTO | CORCHETE_ABIERTO | ASTERISCO | CARET | DOLAR => Simbolo::Operador(c),
+            _ => Simbolo::Literal(c),
+        }
+    }
+
+    ///Interpreta el caracter leído según el dialecto de la expresión regular, de forma que
+    ///el resto del análisis es el mismo para todos los dialectos.
+    fn leer_simbolo(&mut self, c: char, anteriores: &[NodoAst]) -> Simbolo {
+        match self.dialecto {
+            Dialecto::Basico => self.leer_simbolo_basico(c, anteriores),
+            Dialecto::Extendido | Dialecto::Perl => leer_simbolo_extendido(c),
+        }
+    }
+
+    ///Analiza un símbolo, que comienza en la posición inicio, y agrega su nodo a los de la alternativa.
+    /// - Si es un literal, se agrega un nodo con el literal.
+    /// - Si es un punto, un caret o un dolar, se agrega el nodo correspondiente.
+    /// - Si es una repetición (una llave abierta, un asterisco, un signo de interrogación o
+    ///   un signo de más), se aplica sobre el último nodo.
+    /// - Si es un corchete abierto, se agrega un nodo con la clase de caracteres del corchete.
+    /// - Si es una barra, se agrega el nodo del caracter escapado.
+    /// - Si es un paréntesis abierto, se agrega un nodo con el grupo o el modificador.
+    fn analizar_simbolo(
+        &mut self,
+        simbolo: Simbolo,
+        inicio: usize,
+        nodos: &mut Vec<NodoAst>,
+    ) -> Result<(), ErrorEnExpresion> {
+        let tipo = match simbolo {
+            Simbolo::Literal(c) => TipoDeNodo::Caracter(Caracter::Literal(c)),
+            Simbolo::Operador(c @ (LLAVE_ABIERTA | INTERROGACION | ASTERISCO | MAS)) => {
+                return self.aplicar_repeticion(c, inicio, nodos);
+            }
+            Simbolo::Operador(BARRA) => {
+                nodos.push(self.analizar_barra(inicio)?);
+                return Ok(());
+            }
+            Simbolo::Operador(PARENTESIS_ABIERTO) => {
+                nodos.push(self.analizar_parentesis(inicio)?);
+                return Ok(());
+            }
+            Simbolo::Operador(CORCHETE_ABIERTO) => {
+                TipoDeNodo::Caracter(Caracter::Serie(self.conseguir_lista()?))
+            }
+            Simbolo::Operador(PUNTO) => TipoDeNodo::Punto,
+            Simbolo::Operador(CARET) => TipoDeNodo::Caret,
+            Simbolo::Operador(DOLAR) => TipoDeNodo::Dolar,
+            Simbolo::Operador(c) => TipoDeNodo::Caracter(Caracter::Literal(c)),
+        };
+        nodos.push(nodo(tipo, inicio, self.posicion()));
+        Ok(())
+    }
+
+    ///Analiza cada una de las alternativas separadas por la función OR.
+    ///Si se está dentro de un grupo, cuyo paréntesis se abre en la posición indicada, se detiene
+    ///en el paréntesis que lo cierra y es un error que la expresión termine antes. Si no,
+    ///es un error encontrar un paréntesis sin abrir.
+    fn analizar_alternativas(
+        &mut self,
+        apertura: Option<usize>,
+    ) -> Result<Vec<Vec<NodoAst>>, ErrorEnExpresion> {
+        let mut alternativas: Vec<Vec<NodoAst>> = vec![Vec::new()];
+        loop {
+            let inicio = self.posicion();
+            let c = match self.chars_iter.next() {
+                Some(c) => c,
+                None => break,
+            };
+            let anteriores = alternativas
+                .last()
+                .map_or(&[][..], |nodos| nodos.as_slice());
+            match self.leer_simbolo(c, anteriores) {
+                Simbolo::Operador(PARENTESIS_CERRADO) if apertura.is_some() => {
+                    return Ok(alternativas);
+                }
+                Simbolo::Operador(PARENTESIS_CERRADO) => {
+                    return Err(self.error(Error::ErrorEnParentesis, inicio, self.posicion()));
+                }
+                Simbolo::Operador(FUNCION_OR) => alternativas.push(Vec::new()),
+                simbolo => {
+                    if let Some(nodos) = alternativas.last_mut() {
+                        self.analizar_simbolo(simbolo, inicio, nodos)?;
+                    }
+                }
+            }
+        }
+        match apertura {
+            Some(apertura) => Err(self.error_en_caracter(Error::ErrorEnParentesis, apertura)),
+            None => Ok(alternativas),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use crate::configuracion::Configuracion;
+
+///Representa los argumentos con los que se ejecuta el programa, ya separados:
+///la configuración armada con las opciones, los patrones a buscar y el archivo en el que buscarlos.
+///Una línea del archivo se muestra si alguno de los patrones coincide con ella.
+#[derive(Debug, PartialEq)]
+pub struct Argumentos {
+    pub configuracion: Configuracion,
+    pub patrones: Vec<String>,
+    pub archivo: String,
+}
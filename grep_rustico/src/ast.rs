@@ -0,0 +1,43 @@
+use crate::caracter::Caracter;
+use crate::grupo::TipoDeGrupo;
+use crate::modificadores::Modificadores;
+use crate::repeticion::Repeticion;
+
+#[derive(Clone, Debug, PartialEq)]
+///Representa lo que se escribió en una parte de la expresión regular, sin interpretar todavía
+///las opciones de la configuración ni los modificadores.
+/// - Caracter: un literal, una serie, una referencia o un límite de palabra, que se evalúan
+///   igual sin importar los modificadores.
+/// - Punto, Caret y Dolar: el comodín y las anclas, que según los modificadores (?s) y (?m)
+///   pueden incluir los saltos de línea.
+/// - Repeticion: un nodo seguido de una repetición, como en a* o (ab){2}.
+/// - Grupo: una subexpresión entre paréntesis, con sus alternativas, su número de captura,
+///   su tipo y los modificadores que se aplican solo dentro de ella, como en (?i:...).
+/// - Modificadores: un modificador como (?i), que se aplica hasta el final del grupo en el que está.
+pub enum TipoDeNodo {
+    Caracter(Caracter),
+    Punto,
+    Caret,
+    Dolar,
+    Repeticion {
+        nodo: Box<NodoAst>,
+        repeticiones: Repeticion,
+    },
+    Grupo {
+        alternativas: Vec<Vec<NodoAst>>,
+        captura: Option<usize>,
+        tipo: TipoDeGrupo,
+        modificadores: Modificadores,
+    },
+    Modificadores(Modificadores),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+///Representa un nodo del árbol de sintaxis de una expresión regular, que se obtiene al analizarla
+///antes de construir sus pasos. Además de lo que se escribió, guarda la posición en bytes
+///(inicio, fin) de esa parte de la expresión.
+pub struct NodoAst {
+    pub(crate) tipo: TipoDeNodo,
+    pub(crate) inicio: usize,
+    pub(crate) fin: usize,
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq)]
+///Representa las capturas de una coincidencia de la expresión regular en un texto.
+///La captura 0 corresponde a la coincidencia completa y las siguientes a cada grupo
+///entre paréntesis, numerados según el orden en que se abren.
+///Las posiciones se guardan en bytes, como un par (inicio, fin).
+///Además se guarda el nombre de cada grupo, para poder buscarlos por nombre.
+pub struct Captures<'t> {
+    texto: &'t str,
+    posiciones: Vec<Option<(usize, usize)>>,
+    nombres: Arc<Vec<Option<String>>>,
+}
+
+impl<'t> Captures<'t> {
+    pub(crate) fn new(
+        texto: &'t str,
+        posiciones: Vec<Option<(usize, usize)>>,
+        nombres: Arc<Vec<Option<String>>>,
+    ) -> Self {
+        Captures {
+            texto,
+            posiciones,
+            nombres,
+        }
+    }
+
+    ///Devuelve el texto capturado por el grupo indicado.
+    ///Si el grupo no existe o no participó de la coincidencia, devuelve None.
+    pub fn get(&self, grupo: usize) -> Option<&'t str> {
+        self.span(grupo)
+            .map(|(inicio, fin)| &self.texto[inicio..fin])
+    }
+
+    ///Devuelve el texto capturado por el grupo con el nombre indicado.
+    ///Si no hay un grupo con ese nombre o no participó de la coincidencia, devuelve None.
+    pub fn name(&self, nombre: &str) -> Option<&'t str> {
+        let grupo = self
+            .nombres
+            .iter()
+            .position(|n| n.as_deref() == Some(nombre))?;
+        self.get(grupo)
+    }
+
+    ///Devuelve la posición en bytes (inicio, fin) del texto capturado por el grupo indicado.
+    ///Si el grupo no existe o no participó de la coincidencia, devuelve None.
+    pub fn span(&self, grupo: usize) -> Option<(usize, usize)> {
+        self.posiciones.get(grupo).copied().flatten()
+    }
+
+    ///Devuelve la cantidad de grupos, contando la coincidencia completa.
+    pub fn len(&self) -> usize {
+        self.posiciones.len()
+    }
+
+    ///Indica si no hay grupos. Nunca ocurre, ya que siempre está la coincidencia completa.
+    pub fn is_empty(&self) -> bool {
+        self.posiciones.is_empty()
+    }
+}
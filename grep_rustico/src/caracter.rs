@@ -1,30 +1,48 @@
 use crate::clase_char::ClaseChar;
+use crate::mayusculas::iguales_sin_mayusculas;
+
+const SALTO_DE_LINEA: char = '\n';
 
 #[derive(Clone, Debug, PartialEq)]
-///Representa un caracter que puede ser un literal, un comodín, una serie o un dolar.
+///Representa un caracter que puede ser un literal, un comodín, una serie, un dolar, un caret,
+///una referencia o un límite de palabra.
 /// - El literal es un caracter que se espera que sea exactamente igual al que se está comparando.
-/// - El comodín es un caracter que puede ser cualquier caracter.
+/// - El comodín es un caracter que puede ser cualquier caracter salvo un salto de línea,
+///   mientras que ComodinConSaltos también puede ser un salto de línea, como con (?s).
 /// - La serie es un caracter que puede ser cualquier caracter de una clase de caracteres.
 /// - El dolar es un caracter que se espera que sea el final de la cadena.
+/// - El caret es un caracter que se espera que sea el inicio de la cadena.
+/// - InicioDeLinea y FinDeLinea son el caret y el dolar de (?m): además del inicio y el final
+///   de la cadena, se cumplen después y antes de cada salto de línea.
+/// - La referencia es un caracter que se espera que repita lo capturado por un grupo anterior,
+///   por lo que solo la puede evaluar el evaluador, que conoce las capturas.
+/// - Los límites de palabra son caracteres que se espera que estén entre un caracter de palabra
+///   y uno que no lo es (LimitePalabra), o que no lo estén (NoLimitePalabra). InicioPalabra y
+///   FinPalabra además exigen que la palabra esté después o antes de la posición, respectivamente.
 pub enum Caracter {
     Literal(char),
     Comodin,
+    ComodinConSaltos,
     Serie(ClaseChar),
     Dolar,
+    Caret,
+    Referencia(usize),
+    LimitePalabra,
+    NoLimitePalabra,
+    InicioPalabra,
+    FinPalabra,
+    InicioDeLinea,
+    FinDeLinea,
 }
 ///Calcula la longitud en bytes de un caracter de una cadena de texto, si pertenece a una clase de caracter.
-fn calcular_longitud_utf8_clase<F>(valor: &str, negado: bool, funcion: F) -> usize
-where
-    F: Fn(char) -> bool,
-{
+fn calcular_longitud_utf8_clase(valor: &str, clase: &ClaseChar, ignorar_mayusculas: bool) -> usize {
     if let Some(c) = valor.chars().next() {
-        if negado {
-            if funcion(c) {
-                0
-            } else {
-                c.len_utf8()
-            }
-        } else if funcion(c) {
+        let pertenece = if ignorar_mayusculas {
+            clase.contiene_sin_mayusculas(c)
+        } else {
+            clase.contiene(c)
+        };
+        if pertenece {
             c.len_utf8()
         } else {
             0
@@ -35,69 +53,79 @@ where
 }
 
 ///Calcula la longitud en bytes de un caracter de una cadena de texto, si es un literal.
-fn calcular_longitud_utf8_literal(valor: &str, l: &char) -> usize {
-    if valor.starts_with(*l) {
-        l.len_utf8()
-    } else {
-        0
+///Sin distinguir mayúsculas, el caracter de la cadena puede tener otra longitud que el literal.
+fn calcular_longitud_utf8_literal(valor: &str, l: &char, ignorar_mayusculas: bool) -> usize {
+    match valor.chars().next() {
+        Some(c) if c == *l || ignorar_mayusculas && iguales_sin_mayusculas(c, *l) => c.len_utf8(),
+        _ => 0,
     }
 }
 
 ///Calcula la longitud en bytes de un caracter de una cadena de texto, si es un comodín.
-fn calcular_longitud_utf8_comodin(valor: &str) -> usize {
-    if let Some(c) = valor.chars().next() {
-        c.len_utf8()
-    } else {
-        0
-    }
-}
-
-///Calcula la longitud en bytes de un caracter de una cadena de texto, si es un dolar.
-fn calcular_longitud_utf8_dolar(valor: &str) -> usize {
-    if valor.chars().next().is_some() {
-        0
-    } else {
-        1
+///Solo si se indica, el comodín coincide con un salto de línea.
+fn calcular_longitud_utf8_comodin(valor: &str, incluye_saltos: bool) -> usize {
+    match valor.chars().next() {
+        Some(SALTO_DE_LINEA) if !incluye_saltos => 0,
+        Some(c) => c.len_utf8(),
+        None => 0,
     }
 }
 
 impl Caracter {
     ///Según el tipo de caracter con el que estemos trabajando, se calcula su longitud en bytes.
-    pub fn coincide(&self, valor: &str) -> usize {
+    ///Si se ignoran las mayúsculas, los literales y las series aceptan el caracter
+    ///tanto en minúscula como en mayúscula.
+    pub fn coincide(&self, valor: &str, ignorar_mayusculas: bool) -> usize {
+        match self {
+            Caracter::Literal(l) => calcular_longitud_utf8_literal(valor, l, ignorar_mayusculas),
+            Caracter::Comodin => calcular_longitud_utf8_comodin(valor, false),
+            Caracter::ComodinConSaltos => calcular_longitud_utf8_comodin(valor, true),
+            Caracter::Serie(clase) => {
+                calcular_longitud_utf8_clase(valor, clase, ignorar_mayusculas)
+            }
+            _ => 0,
+        }
+    }
+
+    ///Indica si el caracter es un ancla, es decir, si en lugar de consumir un caracter
+    ///de la línea verifica una condición sobre la posición en la que se encuentra.
+    pub fn es_ancla(&self) -> bool {
+        matches!(
+            self,
+            Caracter::Dolar
+                | Caracter::Caret
+                | Caracter::LimitePalabra
+                | Caracter::NoLimitePalabra
+                | Caracter::InicioPalabra
+                | Caracter::FinPalabra
+                | Caracter::InicioDeLinea
+                | Caracter::FinDeLinea
+        )
+    }
+
+    ///Verifica si un ancla se cumple en la posición index de la línea,
+    ///según el caracter anterior y el siguiente a esa posición.
+    pub fn se_cumple_en(&self, linea: &str, index: usize) -> bool {
+        let anterior = linea[..index]
+            .chars()
+            .next_back()
+            .is_some_and(|c| ClaseChar::Palabra(false).contiene(c));
+        let siguiente = linea[index..]
+            .chars()
+            .next()
+            .is_some_and(|c| ClaseChar::Palabra(false).contiene(c));
         match self {
-            Caracter::Literal(l) => calcular_longitud_utf8_literal(valor, l),
-            Caracter::Comodin => calcular_longitud_utf8_comodin(valor),
-            Caracter::Serie(clase) => match clase {
-                ClaseChar::Alpha(negado) => {
-                    calcular_longitud_utf8_clase(valor, *negado, |c: char| {
-                        char::is_ascii_alphabetic(&c)
-                    })
-                }
-                ClaseChar::Alnum(negado) => {
-                    calcular_longitud_utf8_clase(valor, *negado, char::is_alphanumeric)
-                }
-                ClaseChar::Digit(negado) => {
-                    calcular_longitud_utf8_clase(valor, *negado, |c| c.is_ascii_digit())
-                }
-                ClaseChar::Lower(negado) => {
-                    calcular_longitud_utf8_clase(valor, *negado, char::is_lowercase)
-                }
-                ClaseChar::Upper(negado) => {
-                    calcular_longitud_utf8_clase(valor, *negado, char::is_uppercase)
-                }
-                ClaseChar::Space(negado) => {
-                    calcular_longitud_utf8_clase(valor, *negado, char::is_whitespace)
-                }
-                ClaseChar::Punct(negado) => {
-                    calcular_longitud_utf8_clase(valor, *negado, |c: char| {
-                        char::is_ascii_punctuation(&c)
-                    })
-                }
-                ClaseChar::Simple(list, negado) => {
-                    calcular_longitud_utf8_clase(valor, *negado, |c| list.contains(&c))
-                }
-            },
-            Caracter::Dolar => calcular_longitud_utf8_dolar(valor),
+            Caracter::Dolar => index == linea.len(),
+            Caracter::Caret => index == 0,
+            Caracter::LimitePalabra => anterior != siguiente,
+            Caracter::NoLimitePalabra => anterior == siguiente,
+            Caracter::InicioPalabra => !anterior && siguiente,
+            Caracter::FinPalabra => anterior && !siguiente,
+            Caracter::InicioDeLinea => index == 0 || linea[..index].ends_with(SALTO_DE_LINEA),
+            Caracter::FinDeLinea => {
+                index == linea.len() || linea[index..].starts_with(SALTO_DE_LINEA)
+            }
+            _ => false,
         }
     }
 }
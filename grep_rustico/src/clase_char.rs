@@ -1,10 +1,22 @@
 use std::collections::HashSet;
 
+use crate::mayusculas::variantes;
+use crate::tablas_unicode::{categoria_general, escritura};
+
 #[derive(Clone, Debug, PartialEq)]
 ///Representa una clase de caracteres que puede ser alfanumérica,
-///alfabética, numérica, minúscula, mayúscula, espacio o signo de puntuación,
+///alfabética, numérica, minúscula, mayúscula, espacio, signo de puntuación,
+///dígito hexadecimal, caracter de control, caracter visible, caracter imprimible,
+///espacio en blanco horizontal (espacio o tabulación) o de palabra (alfanumérica o guión bajo),
 ///que son las clases que soporta nuestro grep rústico.
-///Además, se agrega una clase que representa un conjunto de caracteres simples.
+///También están las clases de propiedades de Unicode: una categoría general (como L o Lu)
+///o una escritura (como Greek o Han).
+///Además, se agrega una clase que representa un conjunto de caracteres simples, con los
+///literales y los rangos (inicio y fin inclusive) de un corchete, y una clase compuesta,
+///que es la unión de otras clases. Los rangos no se expanden, ya que pueden ser muy grandes.
+///El booleano de cada clase indica si está negada.
+///Las clases de letras, espacios y signos de puntuación siguen las propiedades de Unicode,
+///mientras que los dígitos (decimales y hexadecimales) son solo los de ASCII, como en POSIX.
 pub enum ClaseChar {
     Alnum(bool),
     Alpha(bool),
@@ -13,5 +25,102 @@ pub enum ClaseChar {
     Upper(bool),
     Space(bool),
     Punct(bool),
-    Simple(HashSet<char>, bool),
+    Xdigit(bool),
+    Cntrl(bool),
+    Graph(bool),
+    Print(bool),
+    Blank(bool),
+    Palabra(bool),
+    Categoria(&'static str, bool),
+    Escritura(&'static str, bool),
+    Simple(HashSet<char>, Vec<(char, char)>, bool),
+    Compuesta(Vec<ClaseChar>, bool),
+}
+
+///Aplica la negación de una clase al resultado de verificar si el caracter pertenece a ella.
+fn aplicar_negacion(pertenece: bool, negado: bool) -> bool {
+    pertenece != negado
+}
+
+impl ClaseChar {
+    ///Determina si un caracter pertenece a la clase, teniendo en cuenta si está negada.
+    pub fn contiene(&self, c: char) -> bool {
+        match self {
+            ClaseChar::Alpha(negado) => aplicar_negacion(c.is_alphabetic(), *negado),
+            ClaseChar::Alnum(negado) => aplicar_negacion(c.is_alphanumeric(), *negado),
+            ClaseChar::Digit(negado) => aplicar_negacion(c.is_ascii_digit(), *negado),
+            ClaseChar::Lower(negado) => aplicar_negacion(c.is_lowercase(), *negado),
+            ClaseChar::Upper(negado) => aplicar_negacion(c.is_uppercase(), *negado),
+            ClaseChar::Space(negado) => aplicar_negacion(c.is_whitespace(), *negado),
+            ClaseChar::Punct(negado) => aplicar_negacion(
+                !c.is_control() && !c.is_whitespace() && !c.is_alphanumeric(),
+                *negado,
+            ),
+            ClaseChar::Xdigit(negado) => aplicar_negacion(c.is_ascii_hexdigit(), *negado),
+            ClaseChar::Cntrl(negado) => aplicar_negacion(c.is_control(), *negado),
+            ClaseChar::Graph(negado) => {
+                aplicar_negacion(!c.is_control() && !c.is_whitespace(), *negado)
+            }
+            ClaseChar::Print(negado) => aplicar_negacion(!c.is_control(), *negado),
+            ClaseChar::Blank(negado) => aplicar_negacion(c == ' ' || c == '\t', *negado),
+            ClaseChar::Palabra(negado) => {
+                aplicar_negacion(c.is_alphanumeric() || c == '_', *negado)
+            }
+            ClaseChar::Categoria(nombre, negado) => {
+                aplicar_negacion(categoria_general(c).starts_with(nombre), *negado)
+            }
+            ClaseChar::Escritura(nombre, negado) => {
+                aplicar_negacion(escritura(c) == Some(*nombre), *negado)
+            }
+            ClaseChar::Simple(literales, rangos, negado) => aplicar_negacion(
+                literales.contains(&c)
+                    || rangos
+                        .iter()
+                        .any(|(inicio, fin)| (*inicio..=*fin).contains(&c)),
+                *negado,
+            ),
+            ClaseChar::Compuesta(clases, negado) => {
+                aplicar_negacion(clases.iter().any(|clase| clase.contiene(c)), *negado)
+            }
+        }
+    }
+
+    ///Determina si un caracter pertenece a la clase sin distinguir mayúsculas de minúsculas.
+    ///El caracter pertenece si alguna de sus variantes (en minúscula o en mayúscula) pertenece
+    ///a la clase sin negar, y recién después se aplica la negación, por lo que [^a] no acepta la A.
+    pub fn contiene_sin_mayusculas(&self, c: char) -> bool {
+        let negado = self.esta_negada();
+        let pertenece = match self {
+            ClaseChar::Compuesta(clases, _) => {
+                clases.iter().any(|clase| clase.contiene_sin_mayusculas(c))
+            }
+            _ => variantes(c)
+                .iter()
+                .any(|variante| aplicar_negacion(self.contiene(*variante), negado)),
+        };
+        aplicar_negacion(pertenece, negado)
+    }
+
+    ///Indica si la clase está negada.
+    fn esta_negada(&self) -> bool {
+        match self {
+            ClaseChar::Alnum(negado)
+            | ClaseChar::Alpha(negado)
+            | ClaseChar::Digit(negado)
+            | ClaseChar::Lower(negado)
+            | ClaseChar::Upper(negado)
+            | ClaseChar::Space(negado)
+            | ClaseChar::Punct(negado)
+            | ClaseChar::Xdigit(negado)
+            | ClaseChar::Cntrl(negado)
+            | ClaseChar::Graph(negado)
+            | ClaseChar::Print(negado)
+            | ClaseChar::Blank(negado)
+            | ClaseChar::Palabra(negado)
+            | ClaseChar::Categoria(_, negado)
+            | ClaseChar::Escritura(_, negado)
+            | ClaseChar::Simple(_, _, negado)
+            | ClaseChar::Compuesta(_, negado) => *negado,
+        }
+    }
 }
@@ -0,0 +1,44 @@
+use crate::dialecto::Dialecto;
+
+///Cantidad máxima de repeticiones que se puede indicar entre llaves si no se configura otra,
+///igual al RE_DUP_MAX que define POSIX.
+pub const MAXIMO_REPETICIONES: usize = 32767;
+
+///Opciones con las que se construye una expresión regular.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Configuracion {
+    ///Cantidad máxima de repeticiones que se puede indicar entre llaves, como en a{2,15}.
+    pub maximo_repeticiones: usize,
+    ///Indica si se compara sin distinguir mayúsculas de minúsculas, como con la opción -i.
+    ///Dentro de la expresión se puede cambiar con (?i) y (?-i).
+    pub ignorar_mayusculas: bool,
+    ///Dialecto en el que está escrita la expresión. Si no se indica otro, es el de Perl,
+    ///que incluye todo lo que soporta nuestro grep rústico.
+    pub dialecto: Dialecto,
+    ///Indica si los patrones se toman como cadenas fijas en lugar de expresiones regulares,
+    ///como con la opción -F.
+    pub cadenas_fijas: bool,
+    ///Indica si el punto también coincide con un salto de línea.
+    ///Dentro de la expresión se puede cambiar con (?s) y (?-s).
+    pub punto_incluye_saltos: bool,
+    ///Indica si el ^ y el $ se cumplen al inicio y al final de cada línea, y no solo del texto.
+    ///Dentro de la expresión se puede cambiar con (?m) y (?-m).
+    pub anclas_por_linea: bool,
+    ///Indica si se busca en todo el texto del archivo en lugar de línea por línea,
+    ///como con la opción -U, para que una coincidencia pueda abarcar varias líneas.
+    pub texto_completo: bool,
+}
+
+impl Default for Configuracion {
+    fn default() -> Self {
+        Configuracion {
+            maximo_repeticiones: MAXIMO_REPETICIONES,
+            ignorar_mayusculas: false,
+            dialecto: Dialecto::Perl,
+            cadenas_fijas: false,
+            punto_incluye_saltos: false,
+            anclas_por_linea: false,
+            texto_completo: false,
+        }
+    }
+}
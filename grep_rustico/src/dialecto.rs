@@ -0,0 +1,13 @@
+///Representa el dialecto en el que está escrita una expresión regular.
+/// - Basico (BRE, opción -G): los operadores son \{ \}, \( \), \|, \+ y \?, mientras que
+///   sin la barra esos caracteres son literales. El * al comienzo, el ^ fuera del comienzo
+///   y el $ fuera del final también son literales.
+/// - Extendido (ERE, opción -E): los operadores se escriben sin barra, como en a{2}, (a|b), a+ y a?.
+/// - Perl (opción -P): además de lo anterior, admite repeticiones perezosas y posesivas,
+///   grupos especiales con (?...), clases como \d y \p{L} y secuencias de escape como \t o \x41.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Dialecto {
+    Basico,
+    Extendido,
+    Perl,
+}
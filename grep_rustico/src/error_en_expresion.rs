@@ -0,0 +1,56 @@
+use std::fmt;
+
+use crate::errors::Error;
+
+const MARCA: char = '^';
+
+#[derive(Debug, PartialEq)]
+///Representa un error al analizar una expresión regular, junto con el lugar en el que ocurrió.
+///Se guarda el tipo de error, la expresión completa y la posición en bytes (inicio, fin)
+///de la parte de la expresión que lo provocó, para poder señalarla al usuario.
+pub struct ErrorEnExpresion {
+    tipo: Error,
+    expresion: String,
+    inicio: usize,
+    fin: usize,
+}
+
+impl ErrorEnExpresion {
+    pub(crate) fn new(tipo: Error, expresion: &str, inicio: usize, fin: usize) -> Self {
+        ErrorEnExpresion {
+            tipo,
+            expresion: expresion.to_string(),
+            inicio,
+            fin: fin.max(inicio),
+        }
+    }
+
+    ///Devuelve el tipo de error, que indica qué salió mal.
+    pub fn tipo(&self) -> Error {
+        self.tipo
+    }
+
+    ///Devuelve la posición en bytes (inicio, fin) de la parte de la expresión que provocó el error.
+    ///Si el error está en el final de la expresión, como un paréntesis que falta, el inicio
+    ///y el fin son el largo de la expresión.
+    pub fn span(&self) -> (usize, usize) {
+        (self.inicio, self.fin)
+    }
+}
+
+impl fmt::Display for ErrorEnExpresion {
+    ///Muestra el mensaje del error y, debajo, la expresión con una marca debajo de cada
+    ///caracter que lo provocó, o luego del último si el error está en el final.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let espacios = self.expresion[..self.inicio].chars().count();
+        let marcas = self.expresion[self.inicio..self.fin].chars().count().max(1);
+        writeln!(f, "{}", self.tipo)?;
+        writeln!(f, "{}", self.expresion)?;
+        write!(
+            f,
+            "{}{}",
+            " ".repeat(espacios),
+            MARCA.to_string().repeat(marcas)
+        )
+    }
+}
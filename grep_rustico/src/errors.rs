@@ -1,6 +1,6 @@
 use std::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 ///Crater de errores personalizados para casos específicos.
 ///Cada uno con un mensaje que le dará al usuario una idea de lo que salió mal.
 pub enum Error {
@@ -8,11 +8,21 @@ pub enum Error {
     FallaLecturaArchivo,
     ArgumentosInvalidos,
     CaracterNoProcesable,
-    FormatoDeLineaNoASCII,
     ErrorEnLlaves,
     ErrorEnCorchetes,
     ErrorEnRepeticion,
     ErrorEnFuncionOR,
+    ErrorEnParentesis,
+    ErrorEnNombreDeGrupo,
+    ErrorEnReferencia,
+    ErrorEnClase,
+    LlavesVacias,
+    LlavesConContenidoInvalido,
+    RangoDeLlavesInvertido,
+    RepeticionesExcedidas,
+    ErrorEnEscape,
+    ExpresionDemasiadoGrande,
+    BacktrackingExcedido,
 }
 
 impl fmt::Display for Error {
@@ -26,9 +36,6 @@ impl fmt::Display for Error {
             Error::CaracterNoProcesable => {
                 write!(f, "Error: Ingresaste un caracter no posible de procesar.")
             }
-            Error::FormatoDeLineaNoASCII => {
-                write!(f, "Error: La linea ingresada no esta en formato ASCII.")
-            }
             Error::ErrorEnLlaves => {
                 write!(
                     f,
@@ -44,6 +51,63 @@ impl fmt::Display for Error {
             Error::ErrorEnFuncionOR => {
                 write!(f, "Error: No se cumple con el formato para el uso de la funcion OR correctamente.")
             }
+            Error::ErrorEnParentesis => {
+                write!(f, "Error: No se cumple con el formato para el uso de los paréntesis correctamente.")
+            }
+            Error::ErrorEnNombreDeGrupo => {
+                write!(
+                    f,
+                    "Error: El nombre del grupo de captura no es válido o está repetido."
+                )
+            }
+            Error::ErrorEnReferencia => {
+                write!(
+                    f,
+                    "Error: La referencia no corresponde a un grupo de captura anterior."
+                )
+            }
+            Error::ErrorEnClase => {
+                write!(f, "Error: La clase de caracteres indicada no existe.")
+            }
+            Error::LlavesVacias => {
+                write!(
+                    f,
+                    "Error: Las llaves deben indicar al menos una cantidad de repeticiones."
+                )
+            }
+            Error::LlavesConContenidoInvalido => {
+                write!(
+                    f,
+                    "Error: Las llaves solo pueden contener números separados por una coma."
+                )
+            }
+            Error::RangoDeLlavesInvertido => {
+                write!(
+                    f,
+                    "Error: El mínimo de repeticiones entre llaves es mayor que el máximo."
+                )
+            }
+            Error::RepeticionesExcedidas => {
+                write!(
+                    f,
+                    "Error: La cantidad de repeticiones entre llaves supera el máximo permitido."
+                )
+            }
+            Error::ErrorEnEscape => {
+                write!(f, "Error: La secuencia de escape no es válida.")
+            }
+            Error::ExpresionDemasiadoGrande => {
+                write!(
+                    f,
+                    "Error: La expresión regular es demasiado grande para procesarla."
+                )
+            }
+            Error::BacktrackingExcedido => {
+                write!(
+                    f,
+                    "Error: La evaluación de la expresión regular superó el límite de pasos de backtracking."
+                )
+            }
         }
     }
 }
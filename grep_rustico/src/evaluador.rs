@@ -0,0 +1,295 @@
+use crate::caracter::Caracter;
+use crate::errors::Error;
+use crate::instruccion::Instruccion;
+use crate::mayusculas::iguales_sin_mayusculas;
+use crate::paso_evaluado::PasoEvaluado;
+use crate::programa::Programa;
+
+///Cantidad de instrucciones que se pueden evaluar en una búsqueda, a la que se suman
+///PASOS_POR_BYTE por cada byte de la línea, para que las líneas largas se puedan recorrer.
+const MAXIMO_PASOS: usize = 1_000_000;
+const PASOS_POR_BYTE: usize = 1_000;
+
+///Cantidad máxima de pares de división recordable y posición que se guardan en una búsqueda.
+///Si la línea es tan larga que se superaría, no se recuerda ninguna división.
+const MAXIMO_RECORDADAS: usize = 1 << 24;
+
+///Evalúa un programa sobre un texto mediante backtracking, para las expresiones que no puede
+///evaluar la máquina de Pike: las que tienen referencias, afirmaciones, grupos atómicos o
+///repeticiones posesivas.
+///Recorre las instrucciones en orden y guarda en una pila los pasos evaluados: los caminos
+///que quedan por probar y los valores de las ranuras antes de cambiarlos. Si una instrucción
+///no se cumple, vuelve al último camino guardado, restaurando las ranuras.
+///Como la pila está en el heap, el largo de la línea no está limitado por la pila del programa.
+///Como hay expresiones en las que la cantidad de caminos crece de forma exponencial con el largo
+///de la línea, se recuerda en qué posiciones ya se evaluaron las divisiones recordables del
+///programa, que si se vuelven a alcanzar es porque fallaron, y además la cantidad de
+///instrucciones que se evalúan en una búsqueda está limitada.
+pub(crate) struct Evaluador<'a> {
+    programa: &'a Programa,
+    linea: &'a str,
+    ranuras: Vec<Option<usize>>,
+    pila: Vec<PasoEvaluado>,
+    pasos_restantes: usize,
+    desde: usize,
+    evaluadas: Vec<bool>,
+}
+
+impl<'a> Evaluador<'a> {
+    pub(crate) fn new(programa: &'a Programa, linea: &'a str) -> Self {
+        Evaluador {
+            programa,
+            linea,
+            ranuras: Vec::new(),
+            pila: Vec::new(),
+            pasos_restantes: MAXIMO_PASOS
+                .saturating_add(PASOS_POR_BYTE.saturating_mul(linea.len())),
+            desde: 0,
+            evaluadas: Vec::new(),
+        }
+    }
+
+    ///Busca la primera coincidencia que empieza en la posición desde o después,
+    ///probando cada posición de la línea como inicio, y devuelve las posiciones guardadas
+    ///en cada ranura.
+    ///Devuelve un error si se supera la cantidad de pasos permitida.
+    pub(crate) fn buscar(&mut self, desde: usize) -> Result<Option<Vec<Option<usize>>>, Error> {
+        let linea = self.linea;
+        let cantidad = self.programa.cantidad_recordables * (linea.len() - desde + 1);
+        if cantidad <= MAXIMO_RECORDADAS {
+            self.desde = desde;
+            self.evaluadas = vec![false; cantidad];
+        }
+        for inicio in (desde..=linea.len()).filter(|inicio| linea.is_char_boundary(*inicio)) {
+            if self.coincidir(inicio)? {
+                return Ok(Some(self.ranuras.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    ///Evalúa el programa desde la posición inicio de la línea, hasta llegar a la coincidencia
+    ///o hasta que no queden caminos por probar.
+    fn coincidir(&mut self, inicio: usize) -> Result<bool, Error> {
+        self.ranuras = vec![None; self.programa.cantidad_ranuras];
+        self.pila.clear();
+        let mut instruccion = 0;
+        let mut index = inicio;
+        loop {
+            self.pasos_restantes = self
+                .pasos_restantes
+                .checked_sub(1)
+                .ok_or(Error::BacktrackingExcedido)?;
+            let se_cumple = match &self.programa.instrucciones[instruccion] {
+                Instruccion::Caracter {
+                    caracter,
+                    ignorar_mayusculas,
+                } => match self.avance(caracter, *ignorar_mayusculas, index) {
+                    Some(avance) => {
+                        index += avance;
+                        true
+                    }
+                    None => false,
+                },
+                Instruccion::Ancla(ancla) => ancla.se_cumple_en(self.linea, index),
+                Instruccion::Division(..) if self.ya_evaluada(instruccion, index) => false,
+                Instruccion::Division(primera, segunda) => {
+                    self.pila.push(PasoEvaluado::Alternativa {
+                        instruccion: *segunda,
+                        index,
+                    });
+                    instruccion = *primera;
+                    continue;
+                }
+                Instruccion::Salto(destino) => {
+                    instruccion = *destino;
+                    continue;
+                }
+                Instruccion::Guardar(ranura) | Instruccion::InicioDeVuelta(ranura) => {
+                    self.guardar(*ranura, Some(index));
+                    true
+                }
+                Instruccion::Avance(ranura, salida) => {
+                    if self.ranuras[*ranura] == Some(index) {
+                        instruccion = *salida;
+                        continue;
+                    }
+                    true
+                }
+                Instruccion::Capturar { captura, ranura } => {
+                    self.guardar(2 * captura, self.ranuras[*ranura]);
+                    self.guardar(2 * captura + 1, Some(index));
+                    true
+                }
+                Instruccion::InicioAtomico => {
+                    self.pila.push(PasoEvaluado::Barrera);
+                    true
+                }
+                Instruccion::FinAtomico => {
+                    self.cortar();
+                    true
+                }
+                Instruccion::InicioNegacion(siguiente) => {
+                    self.pila.push(PasoEvaluado::Negacion {
+                        instruccion: *siguiente,
+                        index,
+                    });
+                    true
+                }
+                Instruccion::FinNegacion => {
+                    self.descartar_negacion();
+                    false
+                }
+                Instruccion::Retroceder => {
+                    if let Some(anterior) = self.posicion_anterior(index) {
+                        self.pila.push(PasoEvaluado::Retroceso {
+                            instruccion: instruccion + 1,
+                            inicio: anterior,
+                        });
+                    }
+                    true
+                }
+                Instruccion::Posicion(ranura) => self.ranuras[*ranura] == Some(index),
+                Instruccion::Volver(ranura) => {
+                    index = self.ranuras[*ranura].unwrap_or(index);
+                    true
+                }
+                Instruccion::Coincidencia => return Ok(true),
+            };
+            if se_cumple {
+                instruccion += 1;
+                continue;
+            }
+            match self.volver_atras() {
+                Some((alternativa, posicion)) => {
+                    instruccion = alternativa;
+                    index = posicion;
+                }
+                None => return Ok(false),
+            }
+        }
+    }
+
+    ///Indica si la división de la instrucción indicada ya se evaluó en la posición index,
+    ///y la marca como evaluada. Si es recordable, lo que ocurre luego de ella depende solo
+    ///de la posición, por lo que si ya se evaluó es que falló, y no hace falta volver a hacerlo.
+    fn ya_evaluada(&mut self, instruccion: usize, index: usize) -> bool {
+        let Some(Some((numero, vuelta))) = self.programa.recordables.get(instruccion) else {
+            return false;
+        };
+        if self.evaluadas.is_empty() || vuelta.is_some_and(|r| self.ranuras[r] == Some(index)) {
+            return false;
+        }
+        let posicion = numero * (self.linea.len() - self.desde + 1) + index - self.desde;
+        std::mem::replace(&mut self.evaluadas[posicion], true)
+    }
+
+    ///Guarda un valor en una ranura, dejando en la pila el anterior para poder restaurarlo.
+    fn guardar(&mut self, ranura: usize, valor: Option<usize>) {
+        let anterior = std::mem::replace(&mut self.ranuras[ranura], valor);
+        self.pila.push(PasoEvaluado::Restaurar {
+            ranura,
+            valor: anterior,
+        });
+    }
+
+    ///Devuelve la posición del caracter anterior a index, si lo hay.
+    fn posicion_anterior(&self, index: usize) -> Option<usize> {
+        self.linea[..index]
+            .chars()
+            .next_back()
+            .map(|c| index - c.len_utf8())
+    }
+
+    ///Saca pasos de la pila, restaurando las ranuras, hasta encontrar un camino por probar,
+    ///y devuelve su instrucción y su posición. Si no quedan caminos, devuelve None.
+    ///Si el camino es una negación, es que lo negado no se cumplió, por lo que se sigue luego de ella.
+    fn volver_atras(&mut self) -> Option<(usize, usize)> {
+        while let Some(paso) = self.pila.pop() {
+            match paso {
+                PasoEvaluado::Restaurar { ranura, valor } => self.ranuras[ranura] = valor,
+                PasoEvaluado::Barrera => {}
+                PasoEvaluado::Alternativa { instruccion, index }
+                | PasoEvaluado::Negacion { instruccion, index } => {
+                    return Some((instruccion, index))
+                }
+                PasoEvaluado::Retroceso {
+                    instruccion,
+                    inicio,
+                } => {
+                    if let Some(anterior) = self.posicion_anterior(inicio) {
+                        self.pila.push(PasoEvaluado::Retroceso {
+                            instruccion,
+                            inicio: anterior,
+                        });
+                    }
+                    return Some((instruccion, inicio));
+                }
+            }
+        }
+        None
+    }
+
+    ///Termina una evaluación atómica: descarta los caminos que quedaban por probar desde su
+    ///barrera, para no volver a evaluarla de otra forma, pero conserva cómo restaurar las ranuras.
+    fn cortar(&mut self) {
+        let mut restauraciones = Vec::new();
+        while let Some(paso) = self.pila.pop() {
+            match paso {
+                PasoEvaluado::Barrera => break,
+                PasoEvaluado::Restaurar { .. } => restauraciones.push(paso),
+                _ => {}
+            }
+        }
+        self.pila.extend(restauraciones.into_iter().rev());
+    }
+
+    ///Lo negado se cumplió: descarta los pasos hasta la negación, restaurando las ranuras,
+    ///para que se vuelva atrás a los caminos anteriores a ella.
+    fn descartar_negacion(&mut self) {
+        while let Some(paso) = self.pila.pop() {
+            match paso {
+                PasoEvaluado::Negacion { .. } => break,
+                PasoEvaluado::Restaurar { ranura, valor } => self.ranuras[ranura] = valor,
+                _ => {}
+            }
+        }
+    }
+
+    ///Calcula cuántos bytes avanza el caracter en la posición index, o None si no coincide.
+    ///Una referencia a un grupo que capturó un texto vacío coincide sin avanzar.
+    fn avance(&self, caracter: &Caracter, ignorar_mayusculas: bool, index: usize) -> Option<usize> {
+        match caracter {
+            Caracter::Referencia(captura) => {
+                let inicio = self.ranuras[2 * captura]?;
+                let fin = self.ranuras[2 * captura + 1]?;
+                let capturado = &self.linea[inicio..fin];
+                if ignorar_mayusculas {
+                    self.avance_sin_mayusculas(capturado, index)
+                } else if self.linea[index..].starts_with(capturado) {
+                    Some(capturado.len())
+                } else {
+                    None
+                }
+            }
+            _ => match caracter.coincide(&self.linea[index..], ignorar_mayusculas) {
+                0 => None,
+                avance => Some(avance),
+            },
+        }
+    }
+
+    ///Calcula cuántos bytes avanza un texto capturado en la posición index, comparando
+    ///sin distinguir mayúsculas, o None si no coincide.
+    fn avance_sin_mayusculas(&self, capturado: &str, index: usize) -> Option<usize> {
+        let mut caracteres = self.linea[index..].chars();
+        let mut avance = 0;
+        for esperado in capturado.chars() {
+            match caracteres.next() {
+                Some(c) if iguales_sin_mayusculas(c, esperado) => avance += c.len_utf8(),
+                _ => return None,
+            }
+        }
+        Some(avance)
+    }
+}
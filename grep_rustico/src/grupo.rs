@@ -0,0 +1,29 @@
+use crate::paso_regex::PasoRegex;
+
+#[derive(Clone, Debug, PartialEq)]
+///Representa un grupo de la expresión regular.
+///Contiene una o más alternativas separadas por la función OR, donde cada alternativa
+///es a su vez una lista de pasos.
+///Si el grupo es de captura, se guarda su número (comenzando en 1, según el orden
+///en que se abren los paréntesis).
+///Además se guarda el tipo de grupo, que determina cómo se evalúan sus alternativas.
+pub struct Grupo {
+    pub(crate) alternativas: Vec<Vec<PasoRegex>>,
+    pub(crate) captura: Option<usize>,
+    pub(crate) tipo: TipoDeGrupo,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+///Representa los tipos de grupo que se pueden abrir con un paréntesis.
+/// - Comun: se evalúa probando cada alternativa, con backtrack.
+/// - Atomico: una vez que se cumple no se vuelve a probar de otra forma.
+/// - Anticipacion: se espera que lo que sigue en la línea cumpla (o no, si es negada)
+///   con el grupo, sin avanzar.
+/// - Retrospeccion: se espera que lo anterior en la línea cumpla (o no, si es negada)
+///   con el grupo, sin avanzar.
+pub enum TipoDeGrupo {
+    Comun,
+    Atomico,
+    Anticipacion { negada: bool },
+    Retrospeccion { negada: bool },
+}
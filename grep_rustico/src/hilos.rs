@@ -0,0 +1,45 @@
+#[derive(Clone, Debug)]
+///Representa un hilo de la máquina de Pike: la instrucción del programa en la que está,
+///las posiciones guardadas en cada ranura y en cuántas vueltas opcionales de grupos que
+///empezaron en la posición actual está, es decir, que todavía no consumieron caracteres.
+pub(crate) struct Hilo {
+    pub(crate) instruccion: usize,
+    pub(crate) ranuras: Vec<Option<usize>>,
+    pub(crate) vueltas_vacias: usize,
+}
+
+///Representa los hilos que están en una misma posición del texto, ordenados por prioridad.
+///Se guarda qué instrucciones ya tienen un hilo, ya que si otro llega a la misma instrucción
+///en la misma posición, tiene menos prioridad y haría lo mismo, por lo que se descarta.
+///Solo se distingue en cuántas vueltas vacías está el hilo, ya que eso decide qué hace en los
+///avances, y no puede estar en más que la profundidad del programa.
+///Así nunca hay más hilos que instrucciones por la profundidad más uno, y el tiempo de la
+///búsqueda es lineal en el texto.
+pub(crate) struct ListaDeHilos {
+    pub(crate) hilos: Vec<Hilo>,
+    visitadas: Vec<bool>,
+    profundidad: usize,
+}
+
+impl ListaDeHilos {
+    pub(crate) fn new(cantidad_instrucciones: usize, profundidad: usize) -> Self {
+        ListaDeHilos {
+            hilos: Vec::new(),
+            visitadas: vec![false; (profundidad + 1) * cantidad_instrucciones],
+            profundidad,
+        }
+    }
+
+    ///Marca la instrucción como visitada por un hilo en la cantidad de vueltas vacías indicada.
+    ///Devuelve false si ya lo estaba.
+    pub(crate) fn visitar(&mut self, instruccion: usize, vueltas_vacias: usize) -> bool {
+        let posicion = (self.profundidad + 1) * instruccion + vueltas_vacias;
+        !std::mem::replace(&mut self.visitadas[posicion], true)
+    }
+
+    ///Quita todos los hilos y las marcas de las instrucciones visitadas.
+    pub(crate) fn limpiar(&mut self) {
+        self.hilos.clear();
+        self.visitadas.fill(false);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::caracter::Caracter;
+
+#[derive(Clone, Debug, PartialEq)]
+///Representa una instrucción del programa en el que se compila una expresión regular,
+///donde cada posición del programa es un estado del autómata.
+/// - Caracter: consume un caracter del texto si coincide, y sigue con la instrucción siguiente.
+///   Una referencia consume lo que capturó su grupo, que puede ser más de un caracter.
+/// - Ancla: sigue con la instrucción siguiente sin consumir, si el ancla se cumple en la posición.
+/// - Division: sigue por las dos instrucciones indicadas, donde la primera tiene prioridad.
+/// - Salto: sigue por la instrucción indicada.
+/// - Guardar: guarda la posición en la ranura indicada, como el inicio de la coincidencia
+///   o el de una vuelta de un grupo.
+/// - InicioDeVuelta: como Guardar, pero marca que empieza una vuelta opcional de un grupo.
+/// - Avance: si no se consumió ningún caracter desde la posición guardada en la ranura,
+///   sigue por la instrucción indicada, la salida de la repetición, para que una vuelta
+///   opcional vacía no se vuelva a repetir.
+/// - Capturar: termina una vuelta de un grupo de captura, que empezó en la posición guardada
+///   en la ranura indicada.
+/// - InicioAtomico y FinAtomico: lo que está entre ellos se evalúa de forma atómica.
+/// - InicioNegacion y FinNegacion: lo que está entre ellos no se debe cumplir, y si no se
+///   cumple se sigue por la instrucción indicada.
+/// - Retroceder: sigue desde la posición actual o desde alguna anterior, para las retrospecciones.
+/// - Posicion: sigue solo si la posición es la guardada en la ranura indicada.
+/// - Volver: vuelve a la posición guardada en la ranura indicada, para las anticipaciones.
+/// - Coincidencia: la expresión regular se cumplió.
+///
+///Las instrucciones de grupos atómicos, negaciones, retrospecciones y referencias solo
+///las puede evaluar el evaluador con backtracking.
+pub enum Instruccion {
+    Caracter {
+        caracter: Caracter,
+        ignorar_mayusculas: bool,
+    },
+    Ancla(Caracter),
+    Division(usize, usize),
+    Salto(usize),
+    Guardar(usize),
+    InicioDeVuelta(usize),
+    Avance(usize, usize),
+    Capturar {
+        captura: usize,
+        ranura: usize,
+    },
+    InicioAtomico,
+    FinAtomico,
+    InicioNegacion(usize),
+    FinNegacion,
+    Retroceder,
+    Posicion(usize),
+    Volver(usize),
+    Coincidencia,
+}
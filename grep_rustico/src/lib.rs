@@ -13,3 +13,43 @@ pub mod paso_evaluado;
 pub mod errors;
 
 pub mod verificacion_inicial;
+
+pub mod evaluador;
+
+pub mod grupo;
+
+pub mod captures;
+
+pub mod configuracion;
+
+pub mod tablas_unicode;
+
+pub mod mayusculas;
+
+pub mod dialecto;
+
+pub mod simbolo;
+
+pub mod aho_corasick;
+
+pub mod argumentos;
+
+pub mod multilinea;
+
+pub mod modificadores;
+
+pub mod error_en_expresion;
+
+pub mod ast;
+
+pub mod analizador;
+
+pub mod traductor;
+
+pub mod instruccion;
+
+pub mod hilos;
+
+pub mod programa;
+
+pub mod maquina_pike;
@@ -1,28 +1,90 @@
 use std::env;
 
-use grep_rustico::{regex::Regex, verificacion_inicial};
+use grep_rustico::{aho_corasick::AhoCorasick, multilinea, regex::Regex, verificacion_inicial};
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let (argumentos, lineas) = match verificacion_inicial::verificar_inicio(env::args().collect()) {
+        Ok(resultado) => resultado,
+        Err(error) => {
+            println!("{}", error);
+            return;
+        }
+    };
+    let configuracion = &argumentos.configuracion;
 
-    let lineas = verificacion_inicial::verificar_inicio(args.clone());
+    if configuracion.cadenas_fijas {
+        let automata = AhoCorasick::new(&argumentos.patrones, configuracion.ignorar_mayusculas);
+        if configuracion.texto_completo {
+            let texto = multilinea::unir_lineas(&lineas);
+            let mut marcadas = vec![false; lineas.len()];
+            let coincidencias = automata.coincidencias(&texto);
+            multilinea::marcar_lineas_tocadas(&lineas, &coincidencias, &mut marcadas);
+            imprimir_marcadas(&lineas, marcadas);
+            return;
+        }
+        for l in lineas {
+            if automata.es_valida(l.as_str()) {
+                println!("{}", l);
+            }
+        }
+        return;
+    }
 
-    match lineas {
-        Ok(lineas) => {
-            for l in lineas {
-                match Regex::es_valida_general(&args[1], l.as_str()) {
-                    Ok(result) => {
-                        if result {
-                            println!("{}", l);
-                        }
-                    }
-                    Err(err) => {
-                        println!("{}", err);
+    let mut regexs: Vec<Regex> = Vec::new();
+    for patron in &argumentos.patrones {
+        match Regex::con_configuracion(patron, configuracion) {
+            Ok(regex) => regexs.push(regex),
+            Err(err) => {
+                println!("{}", err);
+                return;
+            }
+        }
+    }
+    if configuracion.texto_completo {
+        let texto = multilinea::unir_lineas(&lineas);
+        let mut marcadas = vec![false; lineas.len()];
+        for regex in &regexs {
+            match regex.coincidencias(&texto) {
+                Ok(coincidencias) => {
+                    multilinea::marcar_lineas_tocadas(&lineas, &coincidencias, &mut marcadas)
+                }
+                Err(err) => {
+                    println!("{}", err);
+                    return;
+                }
+            }
+        }
+        imprimir_marcadas(&lineas, marcadas);
+        return;
+    }
+
+    for l in lineas {
+        let mut coincide = false;
+        for regex in &regexs {
+            match regex.es_valida(l.as_str()) {
+                Ok(result) => {
+                    if result {
+                        coincide = true;
                         break;
                     }
                 }
+                Err(err) => {
+                    println!("{}", err);
+                    return;
+                }
             }
         }
-        Err(error) => println!("{}", error),
-    };
+        if coincide {
+            println!("{}", l);
+        }
+    }
+}
+
+///Imprime las líneas marcadas por alguna coincidencia en todo el texto, con la opción -U.
+fn imprimir_marcadas(lineas: &[String], marcadas: Vec<bool>) {
+    for (l, marcada) in lineas.iter().zip(marcadas) {
+        if marcada {
+            println!("{}", l);
+        }
+    }
 }
@@ -0,0 +1,145 @@
+use crate::hilos::{Hilo, ListaDeHilos};
+use crate::instruccion::Instruccion;
+use crate::programa::Programa;
+
+///Evalúa un programa que no necesita backtracking sobre un texto con la máquina de Pike:
+///en lugar de probar cada camino y volver atrás, avanza a la vez todos los hilos del autómata,
+///de a un caracter del texto.
+///Como en cada posición hay a lo sumo dos hilos por instrucción, el tiempo de la búsqueda es
+///proporcional al largo del programa por el largo del texto.
+///Los hilos se ordenan por prioridad, por lo que la coincidencia es la misma que encontraría
+///el evaluador con backtracking.
+pub(crate) struct MaquinaPike<'a> {
+    programa: &'a Programa,
+    texto: &'a str,
+}
+
+impl<'a> MaquinaPike<'a> {
+    pub(crate) fn new(programa: &'a Programa, texto: &'a str) -> Self {
+        MaquinaPike { programa, texto }
+    }
+
+    ///Busca la primera coincidencia que empieza en la posición desde o después,
+    ///y devuelve las posiciones guardadas en cada ranura.
+    ///En cada posición se agrega un hilo que empieza ahí, con menos prioridad que los que
+    ///empezaron antes, hasta que alguno llega a la coincidencia. Cuando un hilo llega,
+    ///se descartan los de menor prioridad, pero los de mayor prioridad siguen avanzando
+    ///y pueden reemplazar la coincidencia.
+    pub(crate) fn buscar(&self, desde: usize) -> Option<Vec<Option<usize>>> {
+        let cantidad_instrucciones = self.programa.instrucciones.len();
+        let profundidad = self.programa.profundidad;
+        let mut actual = ListaDeHilos::new(cantidad_instrucciones, profundidad);
+        let mut siguiente = ListaDeHilos::new(cantidad_instrucciones, profundidad);
+        let mut coincidencia = None;
+        let mut index = desde;
+        loop {
+            if coincidencia.is_none() {
+                let ranuras = vec![None; self.programa.cantidad_ranuras];
+                let hilo = Hilo {
+                    instruccion: 0,
+                    ranuras,
+                    vueltas_vacias: 0,
+                };
+                self.agregar_hilo(&mut actual, hilo, index);
+            }
+            if actual.hilos.is_empty() && coincidencia.is_some() {
+                break;
+            }
+            let caracter = self.texto[index..].chars().next();
+            for hilo in std::mem::take(&mut actual.hilos) {
+                match &self.programa.instrucciones[hilo.instruccion] {
+                    Instruccion::Coincidencia => {
+                        coincidencia = Some(hilo.ranuras);
+                        break;
+                    }
+                    Instruccion::Caracter {
+                        caracter: esperado,
+                        ignorar_mayusculas,
+                    } => {
+                        if let Some(c) = caracter {
+                            if esperado.coincide(&self.texto[index..], *ignorar_mayusculas) > 0 {
+                                let avanzado = Hilo {
+                                    instruccion: hilo.instruccion + 1,
+                                    ranuras: hilo.ranuras,
+                                    vueltas_vacias: 0,
+                                };
+                                self.agregar_hilo(&mut siguiente, avanzado, index + c.len_utf8());
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            match caracter {
+                Some(c) => index += c.len_utf8(),
+                None => break,
+            }
+            std::mem::swap(&mut actual, &mut siguiente);
+            siguiente.limpiar();
+        }
+        coincidencia
+    }
+
+    ///Agrega a la lista un hilo, siguiendo las instrucciones que no consumen caracteres
+    ///(divisiones, saltos, anclas, ranuras, vueltas y capturas) hasta llegar a un
+    ///caracter o a la coincidencia. Los caminos se recorren en orden de prioridad, para que
+    ///los hilos queden ordenados en la lista.
+    ///Una vuelta opcional de un grupo que no consumió caracteres sale de la repetición en su
+    ///avance, como en el evaluador con backtracking. Si empezó una vuelta en la posición actual,
+    ///también lo hicieron las vueltas internas, por lo que las vueltas vacías son siempre las
+    ///más internas, y basta con contarlas para saber si la que termina está vacía.
+    fn agregar_hilo(&self, lista: &mut ListaDeHilos, hilo: Hilo, index: usize) {
+        let mut pendientes = vec![hilo];
+        while let Some(mut hilo) = pendientes.pop() {
+            if !lista.visitar(hilo.instruccion, hilo.vueltas_vacias) {
+                continue;
+            }
+            match &self.programa.instrucciones[hilo.instruccion] {
+                Instruccion::Division(primera, segunda) => {
+                    pendientes.push(Hilo {
+                        instruccion: *segunda,
+                        ..hilo.clone()
+                    });
+                    hilo.instruccion = *primera;
+                    pendientes.push(hilo);
+                }
+                Instruccion::Salto(destino) => {
+                    hilo.instruccion = *destino;
+                    pendientes.push(hilo);
+                }
+                Instruccion::Ancla(ancla) if ancla.se_cumple_en(self.texto, index) => {
+                    hilo.instruccion += 1;
+                    pendientes.push(hilo);
+                }
+                Instruccion::Guardar(ranura) => {
+                    hilo.ranuras[*ranura] = Some(index);
+                    hilo.instruccion += 1;
+                    pendientes.push(hilo);
+                }
+                Instruccion::InicioDeVuelta(ranura) => {
+                    hilo.ranuras[*ranura] = Some(index);
+                    hilo.vueltas_vacias += 1;
+                    hilo.instruccion += 1;
+                    pendientes.push(hilo);
+                }
+                Instruccion::Avance(_, salida) => {
+                    if hilo.vueltas_vacias > 0 {
+                        hilo.vueltas_vacias -= 1;
+                        hilo.instruccion = *salida;
+                    } else {
+                        hilo.instruccion += 1;
+                    }
+                    pendientes.push(hilo);
+                }
+                Instruccion::Capturar { captura, ranura } => {
+                    hilo.ranuras[2 * captura] = hilo.ranuras[*ranura];
+                    hilo.ranuras[2 * captura + 1] = Some(index);
+                    hilo.instruccion += 1;
+                    pendientes.push(hilo);
+                }
+                Instruccion::Caracter { .. } | Instruccion::Coincidencia => lista.hilos.push(hilo),
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+///Pliega un caracter para compararlo sin distinguir mayúsculas de minúsculas,
+///con el plegado simple de Unicode: se lleva a mayúscula y luego a minúscula,
+///siempre que cada conversión dé un único caracter. Así, por ejemplo,
+///la ſ (s larga) y la S se pliegan a la s, y la ς y la Σ se pliegan a la σ.
+pub(crate) fn plegar(c: char) -> char {
+    let mayuscula = convertir_si_es_unico(c, c.to_uppercase());
+    convertir_si_es_unico(mayuscula, mayuscula.to_lowercase())
+}
+
+///Devuelve el caracter convertido si la conversión dio un único caracter, o el original si no.
+fn convertir_si_es_unico(original: char, mut conversion: impl Iterator<Item = char>) -> char {
+    match (conversion.next(), conversion.next()) {
+        (Some(convertido), None) => convertido,
+        _ => original,
+    }
+}
+
+///Verifica si dos caracteres son iguales sin distinguir mayúsculas de minúsculas.
+pub(crate) fn iguales_sin_mayusculas(a: char, b: char) -> bool {
+    a == b || plegar(a) == plegar(b)
+}
+
+///Devuelve el caracter junto con su versión en minúscula y en mayúscula,
+///para verificar si alguna de ellas pertenece a una clase de caracteres.
+pub(crate) fn variantes(c: char) -> [char; 3] {
+    [
+        c,
+        convertir_si_es_unico(c, c.to_lowercase()),
+        convertir_si_es_unico(c, c.to_uppercase()),
+    ]
+}
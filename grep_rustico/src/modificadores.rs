@@ -0,0 +1,29 @@
+use crate::configuracion::Configuracion;
+
+///Representa los modificadores que se escriben al comienzo de un paréntesis, como en (?i)
+///o en (?s-m:...). Cada uno puede activarse (Some(true)), desactivarse (Some(false))
+///o no indicarse (None), en cuyo caso se mantiene lo que ya estaba configurado.
+/// - ignorar_mayusculas: la i, para no distinguir mayúsculas de minúsculas.
+/// - punto_incluye_saltos: la s, para que el punto también coincida con un salto de línea.
+/// - anclas_por_linea: la m, para que el ^ y el $ se cumplan al inicio y al final de cada línea.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Modificadores {
+    pub ignorar_mayusculas: Option<bool>,
+    pub punto_incluye_saltos: Option<bool>,
+    pub anclas_por_linea: Option<bool>,
+}
+
+impl Modificadores {
+    ///Cambia en la configuración las opciones que indican los modificadores.
+    pub fn aplicar(&self, configuracion: &mut Configuracion) {
+        if let Some(ignorar_mayusculas) = self.ignorar_mayusculas {
+            configuracion.ignorar_mayusculas = ignorar_mayusculas;
+        }
+        if let Some(punto_incluye_saltos) = self.punto_incluye_saltos {
+            configuracion.punto_incluye_saltos = punto_incluye_saltos;
+        }
+        if let Some(anclas_por_linea) = self.anclas_por_linea {
+            configuracion.anclas_por_linea = anclas_por_linea;
+        }
+    }
+}
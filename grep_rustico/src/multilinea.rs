@@ -0,0 +1,67 @@
+///Separador de las líneas al unirlas en un único texto para buscar en todo el archivo.
+pub const SEPARADOR_LINEAS: &str = "\n";
+
+///Une las líneas del archivo en un único texto, separadas por saltos de línea,
+///para que una coincidencia pueda abarcar varias de ellas.
+pub fn unir_lineas(lineas: &[String]) -> String {
+    lineas.join(SEPARADOR_LINEAS)
+}
+
+///Marca las líneas que toca alguna de las coincidencias encontradas en el texto
+///que se obtiene al unirlas. Cada coincidencia es una posición en bytes (inicio, fin).
+/// - Una coincidencia toca todas las líneas desde la de su inicio hasta la de su último caracter,
+///   y el salto de línea con el que termina cada línea forma parte de ella.
+/// - Una coincidencia vacía toca solo la línea en la que se encuentra.
+pub fn marcar_lineas_tocadas(
+    lineas: &[String],
+    coincidencias: &[(usize, usize)],
+    marcadas: &mut [bool],
+) {
+    let mut inicios: Vec<usize> = Vec::with_capacity(lineas.len());
+    let mut inicio = 0;
+    for linea in lineas {
+        inicios.push(inicio);
+        inicio += linea.len() + SEPARADOR_LINEAS.len();
+    }
+    for &(inicio, fin) in coincidencias {
+        let primera = inicios.partition_point(|&i| i <= inicio).saturating_sub(1);
+        let ultima = if fin > inicio {
+            inicios.partition_point(|&i| i < fin).saturating_sub(1)
+        } else {
+            primera
+        };
+        for marcada in marcadas.iter_mut().take(ultima + 1).skip(primera) {
+            *marcada = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_coincidencia_en_varias_lineas() {
+        let lineas = vec!["ab".to_string(), "cd".to_string(), "ef".to_string()];
+        assert_eq!(unir_lineas(&lineas), "ab\ncd\nef");
+        let mut marcadas = vec![false; 3];
+        marcar_lineas_tocadas(&lineas, &[(1, 4)], &mut marcadas);
+        assert_eq!(marcadas, vec![true, true, false]);
+    }
+
+    #[test]
+    fn test02_coincidencia_que_termina_en_salto() {
+        let lineas = vec!["ab".to_string(), "cd".to_string(), "ef".to_string()];
+        let mut marcadas = vec![false; 3];
+        marcar_lineas_tocadas(&lineas, &[(1, 3)], &mut marcadas);
+        assert_eq!(marcadas, vec![true, false, false]);
+    }
+
+    #[test]
+    fn test03_coincidencias_vacias_y_separadas() {
+        let lineas = vec!["ab".to_string(), "".to_string(), "ef".to_string()];
+        let mut marcadas = vec![false; 3];
+        marcar_lineas_tocadas(&lineas, &[(3, 3), (6, 7)], &mut marcadas);
+        assert_eq!(marcadas, vec![false, true, true]);
+    }
+}
@@ -1,9 +1,16 @@
-use crate::paso_regex::PasoRegex;
-///Permite evaluar si un paso de la expresión regular se cumple o no.
-///Además, se guarda la cantidad de caracteres que se matchearon y si es backtrackeable.
+///Representa un paso ya evaluado que se guarda en la pila del evaluador con backtracking,
+///para poder volver atrás cuando una instrucción posterior no se cumple.
+/// - Alternativa: otro camino por el que se puede seguir, desde la instrucción y la posición indicadas.
+/// - Restaurar: el valor que tenía una ranura antes de guardar en ella una posición.
+/// - Barrera: el comienzo de una evaluación atómica, hasta donde se descartan las alternativas.
+/// - Negacion: el comienzo de algo que no se debe cumplir. Si se vuelve hasta acá, es que no
+///   se cumplió, por lo que se sigue desde la instrucción y la posición indicadas.
+/// - Retroceso: la próxima posición anterior desde la que se puede probar una retrospección.
 #[derive(Debug)]
-pub struct PasoEvaluado {
-    pub(crate) paso: PasoRegex,
-    pub(crate) tam_matcheo: usize,
-    pub(crate) backtrackeable: bool,
+pub enum PasoEvaluado {
+    Alternativa { instruccion: usize, index: usize },
+    Restaurar { ranura: usize, valor: Option<usize> },
+    Barrera,
+    Negacion { instruccion: usize, index: usize },
+    Retroceso { instruccion: usize, inicio: usize },
 }
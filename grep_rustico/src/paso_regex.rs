@@ -1,11 +1,25 @@
 pub(crate) use crate::caracter::Caracter;
+use crate::grupo::Grupo;
 use crate::repeticion::Repeticion;
 
-#[derive(Clone, Debug)]
-///Representa un paso de la expresión regular que puede ser un caracter interno y una repetición.
-///El caracter interno es un caracter que se espera que sea exactamente igual al que se está comparando.
-///La repetición es la cantidad de veces que se espera que se repita el caracter interno.
+#[derive(Clone, Debug, PartialEq)]
+///Representa el elemento que se repite en un paso de la expresión regular.
+/// - Caracter: un único caracter (literal, comodín, serie, dolar o caret).
+/// - Grupo: una subexpresión entre paréntesis, compuesta por una o más alternativas
+///   separadas por la función OR, donde cada alternativa es a su vez una lista de pasos.
+pub enum Elemento {
+    Caracter(Caracter),
+    Grupo(Grupo),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+///Representa un paso de la expresión regular que puede ser un elemento interno y una repetición.
+///El elemento interno es un caracter o un grupo de pasos que se espera encontrar en la línea.
+///La repetición es la cantidad de veces que se espera que se repita el elemento interno.
+///Como un grupo contiene otros pasos, la expresión regular queda representada como un árbol.
+///Además, cada paso indica si su caracter se compara sin distinguir mayúsculas de minúsculas.
 pub struct PasoRegex {
-    pub(crate) caracter_interno: Caracter,
+    pub(crate) elemento: Elemento,
     pub(crate) repeticiones: Repeticion,
+    pub(crate) ignorar_mayusculas: bool,
 }
@@ -0,0 +1,365 @@
+use crate::caracter::Caracter;
+use crate::grupo::{Grupo, TipoDeGrupo};
+use crate::instruccion::Instruccion;
+use crate::paso_regex::{Elemento, PasoRegex};
+
+///Cantidad máxima de instrucciones de un programa. Las repeticiones entre llaves se compilan
+///repitiendo su elemento, por lo que repeticiones grandes anidadas pueden dar programas enormes.
+const MAXIMO_INSTRUCCIONES: usize = 1_000_000;
+
+///Representa una expresión regular compilada a un autómata de Thompson, como una lista de
+///instrucciones. Si la expresión no usa referencias, afirmaciones, grupos atómicos ni
+///repeticiones posesivas, se puede evaluar con la máquina de Pike en tiempo lineal; si no,
+///se evalúa con backtracking.
+///Cada captura ocupa dos ranuras (inicio y fin), comenzando por la coincidencia completa,
+///y luego hay una ranura por cada grupo o referencia repetidos, donde se guarda la posición
+///en la que empezó su vuelta.
+///La profundidad es la mayor cantidad de vueltas de grupos o referencias una dentro de otra.
+///Las divisiones recordables son las que el evaluador con backtracking puede recordar en qué
+///posiciones ya evaluó: para cada instrucción que lo es, se guarda su número entre ellas y la
+///ranura de la vuelta opcional más interna que la contiene.
+#[derive(Debug)]
+pub(crate) struct Programa {
+    pub(crate) instrucciones: Vec<Instruccion>,
+    pub(crate) cantidad_ranuras: usize,
+    pub(crate) necesita_backtracking: bool,
+    pub(crate) profundidad: usize,
+    pub(crate) recordables: Vec<Option<(usize, Option<usize>)>>,
+    pub(crate) cantidad_recordables: usize,
+}
+
+impl Programa {
+    ///Compila los pasos de una expresión regular con la cantidad de capturas indicada.
+    ///Devuelve None si el programa supera la cantidad máxima de instrucciones.
+    pub(crate) fn compilar(pasos: &[PasoRegex], cantidad_capturas: usize) -> Option<Self> {
+        let mut programa = Programa {
+            instrucciones: Vec::new(),
+            cantidad_ranuras: 2 * (cantidad_capturas + 1),
+            necesita_backtracking: false,
+            profundidad: 0,
+            recordables: Vec::new(),
+            cantidad_recordables: 0,
+        };
+        programa.agregar(Instruccion::Guardar(0))?;
+        programa.compilar_pasos(pasos, 0)?;
+        programa.agregar(Instruccion::Guardar(1))?;
+        programa.agregar(Instruccion::Coincidencia)?;
+        if programa.necesita_backtracking {
+            programa.buscar_recordables();
+        }
+        Some(programa)
+    }
+
+    ///Busca las divisiones en las que el evaluador con backtracking puede recordar que ya
+    ///evaluó una posición. Lo que ocurre luego de una división depende solo de su posición,
+    ///y no de cómo se llegó a ella, salvo en estos casos:
+    /// - Si hay referencias, que dependen de lo capturado, por lo que no se recuerda ninguna.
+    /// - Dentro de una evaluación atómica o de una negación, donde al terminarla se descartan
+    ///   caminos anteriores a la división, y dentro de una afirmación, que depende de la
+    ///   posición en la que empezó.
+    /// - Si la vuelta opcional más interna que la contiene empezó en la misma posición, ya que
+    ///   su avance sale de la repetición. Esto lo verifica el evaluador con la ranura guardada.
+    ///
+    ///Las vueltas opcionales, las evaluaciones atómicas y las negaciones ocupan instrucciones
+    ///contiguas, por lo que alcanza con recorrer el programa en orden.
+    fn buscar_recordables(&mut self) {
+        let mut abiertas = Vec::new();
+        let mut bloques = 0;
+        let mut cantidad = 0;
+        let mut recordables = Vec::new();
+        for instruccion in &self.instrucciones {
+            let mut recordable = None;
+            match instruccion {
+                Instruccion::Caracter {
+                    caracter: Caracter::Referencia(_),
+                    ..
+                } => return,
+                Instruccion::InicioDeVuelta(ranura) => abiertas.push(*ranura),
+                Instruccion::Avance(..) => {
+                    abiertas.pop();
+                }
+                Instruccion::InicioAtomico | Instruccion::InicioNegacion(_) => bloques += 1,
+                Instruccion::FinAtomico | Instruccion::FinNegacion => bloques -= 1,
+                Instruccion::Division(..) if bloques == 0 => {
+                    recordable = Some((cantidad, abiertas.last().copied()));
+                    cantidad += 1;
+                }
+                _ => {}
+            }
+            recordables.push(recordable);
+        }
+        self.recordables = recordables;
+        self.cantidad_recordables = cantidad;
+    }
+
+    ///Agrega una instrucción y devuelve su posición en el programa.
+    fn agregar(&mut self, instruccion: Instruccion) -> Option<usize> {
+        if self.instrucciones.len() >= MAXIMO_INSTRUCCIONES {
+            return None;
+        }
+        self.instrucciones.push(instruccion);
+        Some(self.instrucciones.len() - 1)
+    }
+
+    ///Reserva la ranura de una vuelta nueva, que está dentro de la cantidad de vueltas
+    ///indicada, y devuelve su número.
+    fn nueva_vuelta(&mut self, profundidad: usize) -> usize {
+        self.profundidad = self.profundidad.max(profundidad + 1);
+        self.cantidad_ranuras += 1;
+        self.cantidad_ranuras - 1
+    }
+
+    ///Completa una división agregada antes, una vez que se conoce la posición de su salida.
+    ///Si la repetición es perezosa, la salida tiene prioridad sobre otra vuelta.
+    fn completar_division(&mut self, division: usize, salida: usize, perezosa: bool) {
+        self.instrucciones[division] = if perezosa {
+            Instruccion::Division(salida, division + 1)
+        } else {
+            Instruccion::Division(division + 1, salida)
+        };
+    }
+
+    fn compilar_pasos(&mut self, pasos: &[PasoRegex], profundidad: usize) -> Option<()> {
+        for paso in pasos {
+            self.compilar_paso(paso, profundidad)?;
+        }
+        Some(())
+    }
+
+    ///Compila un paso según su repetición.
+    /// - Un ancla se evalúa una única vez si la repetición exige al menos una vez,
+    ///   y si puede no estar, como en \b?, no se agrega.
+    /// - Una repetición posesiva se compila como la codiciosa dentro de una evaluación atómica.
+    fn compilar_paso(&mut self, paso: &PasoRegex, profundidad: usize) -> Option<()> {
+        if let Elemento::Caracter(caracter) = &paso.elemento {
+            if caracter.es_ancla() {
+                if paso.repeticiones.limites().0 > 0 {
+                    self.agregar(Instruccion::Ancla(caracter.clone()))?;
+                }
+                return Some(());
+            }
+        }
+        if paso.repeticiones.es_posesiva() {
+            self.necesita_backtracking = true;
+            self.agregar(Instruccion::InicioAtomico)?;
+            self.compilar_repeticion(paso, false, profundidad)?;
+            self.agregar(Instruccion::FinAtomico)?;
+            return Some(());
+        }
+        self.compilar_repeticion(paso, paso.repeticiones.es_perezosa(), profundidad)
+    }
+
+    ///Compila la repetición de un paso: su elemento se compila una única vez y luego se copia
+    ///el mínimo de veces, seguido de las vueltas opcionales.
+    /// - Sin máximo, la vuelta opcional es un ciclo que vuelve a la división.
+    /// - Con máximo, se agregan tantas vueltas opcionales como falten para llegar a él.
+    ///
+    ///Una vuelta opcional que no consume caracteres sale de la repetición, como en PCRE.
+    fn compilar_repeticion(
+        &mut self,
+        paso: &PasoRegex,
+        perezosa: bool,
+        profundidad: usize,
+    ) -> Option<()> {
+        let (min, max) = paso.repeticiones.limites();
+        let origen = self.instrucciones.len();
+        let ranura = self.compilar_vuelta(paso, profundidad)?;
+        let vuelta = self.instrucciones.split_off(origen);
+        for _ in 0..min {
+            self.copiar_vuelta(&vuelta, origen, ranura, false)?;
+        }
+        match max {
+            None => {
+                let division = self.agregar(Instruccion::Salto(0))?;
+                self.copiar_vuelta(&vuelta, origen, ranura, true)?;
+                self.agregar(Instruccion::Salto(division))?;
+                let salida = self.instrucciones.len();
+                self.completar_division(division, salida, perezosa);
+                self.completar_avances(origen, ranura, salida);
+            }
+            Some(max) => {
+                let mut divisiones = Vec::new();
+                for _ in min..max {
+                    divisiones.push(self.agregar(Instruccion::Salto(0))?);
+                    self.copiar_vuelta(&vuelta, origen, ranura, true)?;
+                }
+                let salida = self.instrucciones.len();
+                for division in divisiones {
+                    self.completar_division(division, salida, perezosa);
+                }
+                self.completar_avances(origen, ranura, salida);
+            }
+        }
+        Some(())
+    }
+
+    ///Completa los avances de las vueltas opcionales de una repetición compilada desde la
+    ///posición origen, una vez que se conoce la posición de su salida.
+    fn completar_avances(&mut self, origen: usize, ranura: Option<usize>, salida: usize) {
+        for instruccion in &mut self.instrucciones[origen..salida] {
+            if let Instruccion::Avance(propia, destino) = instruccion {
+                if ranura == Some(*propia) {
+                    *destino = salida;
+                }
+            }
+        }
+    }
+
+    ///Copia al final del programa una vuelta compilada en la posición origen, moviendo
+    ///sus saltos a la nueva posición.
+    ///Si la vuelta no es opcional, no se marca su inicio ni se verifica que avance,
+    ///ya que las vueltas hasta el mínimo pueden no consumir caracteres. Si lo es, la salida
+    ///de su avance se completa luego, cuando se conoce la de la repetición.
+    fn copiar_vuelta(
+        &mut self,
+        vuelta: &[Instruccion],
+        origen: usize,
+        ranura: Option<usize>,
+        opcional: bool,
+    ) -> Option<()> {
+        let destino = self.instrucciones.len();
+        let mover = |posicion: usize| posicion - origen + destino;
+        for (i, instruccion) in vuelta.iter().enumerate() {
+            let copia = match instruccion {
+                Instruccion::Division(primera, segunda) => {
+                    Instruccion::Division(mover(*primera), mover(*segunda))
+                }
+                Instruccion::Salto(posicion) => Instruccion::Salto(mover(*posicion)),
+                Instruccion::InicioNegacion(posicion) => {
+                    Instruccion::InicioNegacion(mover(*posicion))
+                }
+                Instruccion::InicioDeVuelta(propia) if !opcional && ranura == Some(*propia) => {
+                    Instruccion::Guardar(*propia)
+                }
+                Instruccion::Avance(propia, _) if ranura == Some(*propia) => {
+                    if opcional {
+                        instruccion.clone()
+                    } else {
+                        Instruccion::Salto(destino + i + 1)
+                    }
+                }
+                Instruccion::Avance(otra, salida) => Instruccion::Avance(*otra, mover(*salida)),
+                instruccion => instruccion.clone(),
+            };
+            self.agregar(copia)?;
+        }
+        Some(())
+    }
+
+    ///Compila una vuelta opcional del elemento de un paso y devuelve la ranura en la que se
+    ///guarda dónde empezó, si la tiene.
+    ///Las vueltas de un grupo o de una referencia pueden no consumir caracteres, por lo que
+    ///terminan con un avance que sale de la repetición cuando no avanzan, y así un ciclo
+    ///nunca se repite sin consumir. La profundidad es la cantidad de vueltas que la contienen.
+    fn compilar_vuelta(&mut self, paso: &PasoRegex, profundidad: usize) -> Option<Option<usize>> {
+        match &paso.elemento {
+            Elemento::Caracter(caracter @ Caracter::Referencia(_)) => {
+                self.necesita_backtracking = true;
+                let ranura = self.nueva_vuelta(profundidad);
+                self.agregar(Instruccion::InicioDeVuelta(ranura))?;
+                self.agregar(Instruccion::Caracter {
+                    caracter: caracter.clone(),
+                    ignorar_mayusculas: paso.ignorar_mayusculas,
+                })?;
+                self.agregar(Instruccion::Avance(ranura, 0))?;
+                Some(Some(ranura))
+            }
+            Elemento::Caracter(caracter) => {
+                self.agregar(Instruccion::Caracter {
+                    caracter: caracter.clone(),
+                    ignorar_mayusculas: paso.ignorar_mayusculas,
+                })?;
+                Some(None)
+            }
+            Elemento::Grupo(grupo) => {
+                let ranura = self.nueva_vuelta(profundidad);
+                self.agregar(Instruccion::InicioDeVuelta(ranura))?;
+                self.compilar_grupo(grupo, ranura, profundidad + 1)?;
+                if let Some(captura) = grupo.captura {
+                    self.agregar(Instruccion::Capturar { captura, ranura })?;
+                }
+                self.agregar(Instruccion::Avance(ranura, 0))?;
+                Some(Some(ranura))
+            }
+        }
+    }
+
+    ///Compila el contenido de una vuelta de un grupo según su tipo, donde la ranura indicada
+    ///guarda la posición en la que empezó la vuelta.
+    /// - Comun: sus alternativas.
+    /// - Atomico: sus alternativas dentro de una evaluación atómica.
+    /// - Anticipacion: sus alternativas, de forma atómica, y luego se vuelve a la posición
+    ///   del comienzo. Si es negada, sus alternativas dentro de una negación.
+    /// - Retrospeccion: sus alternativas desde alguna posición anterior, que deben terminar
+    ///   en la posición del comienzo, de forma atómica o dentro de una negación.
+    fn compilar_grupo(&mut self, grupo: &Grupo, ranura: usize, profundidad: usize) -> Option<()> {
+        if grupo.tipo != TipoDeGrupo::Comun {
+            self.necesita_backtracking = true;
+        }
+        match grupo.tipo {
+            TipoDeGrupo::Comun => self.compilar_alternativas(&grupo.alternativas, profundidad)?,
+            TipoDeGrupo::Atomico => {
+                self.agregar(Instruccion::InicioAtomico)?;
+                self.compilar_alternativas(&grupo.alternativas, profundidad)?;
+                self.agregar(Instruccion::FinAtomico)?;
+            }
+            TipoDeGrupo::Anticipacion { negada: false } => {
+                self.agregar(Instruccion::InicioAtomico)?;
+                self.compilar_alternativas(&grupo.alternativas, profundidad)?;
+                self.agregar(Instruccion::FinAtomico)?;
+                self.agregar(Instruccion::Volver(ranura))?;
+            }
+            TipoDeGrupo::Anticipacion { negada: true } => {
+                let negacion = self.agregar(Instruccion::InicioNegacion(0))?;
+                self.compilar_alternativas(&grupo.alternativas, profundidad)?;
+                self.agregar(Instruccion::FinNegacion)?;
+                self.instrucciones[negacion] =
+                    Instruccion::InicioNegacion(self.instrucciones.len());
+            }
+            TipoDeGrupo::Retrospeccion { negada: false } => {
+                self.agregar(Instruccion::InicioAtomico)?;
+                self.agregar(Instruccion::Retroceder)?;
+                self.compilar_alternativas(&grupo.alternativas, profundidad)?;
+                self.agregar(Instruccion::Posicion(ranura))?;
+                self.agregar(Instruccion::FinAtomico)?;
+            }
+            TipoDeGrupo::Retrospeccion { negada: true } => {
+                let negacion = self.agregar(Instruccion::InicioNegacion(0))?;
+                self.agregar(Instruccion::Retroceder)?;
+                self.compilar_alternativas(&grupo.alternativas, profundidad)?;
+                self.agregar(Instruccion::Posicion(ranura))?;
+                self.agregar(Instruccion::FinNegacion)?;
+                self.instrucciones[negacion] =
+                    Instruccion::InicioNegacion(self.instrucciones.len());
+            }
+        }
+        Some(())
+    }
+
+    ///Compila las alternativas de un grupo, con una división por cada una salvo la última.
+    fn compilar_alternativas(
+        &mut self,
+        alternativas: &[Vec<PasoRegex>],
+        profundidad: usize,
+    ) -> Option<()> {
+        let mut saltos = Vec::new();
+        for (i, alternativa) in alternativas.iter().enumerate() {
+            let division = if i + 1 < alternativas.len() {
+                Some(self.agregar(Instruccion::Salto(0))?)
+            } else {
+                None
+            };
+            self.compilar_pasos(alternativa, profundidad)?;
+            if let Some(division) = division {
+                saltos.push(self.agregar(Instruccion::Salto(0))?);
+                let siguiente = self.instrucciones.len();
+                self.completar_division(division, siguiente, false);
+            }
+        }
+        let fin = self.instrucciones.len();
+        for salto in saltos {
+            self.instrucciones[salto] = Instruccion::Salto(fin);
+        }
+        Some(())
+    }
+}
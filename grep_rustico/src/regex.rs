@@ -1,1110 +1,2055 @@
-use std::collections::{HashSet, VecDeque};
-use std::str::Chars;
+use std::sync::Arc;
 
-use crate::caracter::Caracter;
-use crate::clase_char::ClaseChar;
+use crate::analizador::Analizador;
+use crate::captures::Captures;
+use crate::configuracion::Configuracion;
+use crate::dialecto::Dialecto;
+use crate::error_en_expresion::ErrorEnExpresion;
 use crate::errors::Error;
-use crate::paso_evaluado::PasoEvaluado;
-use crate::paso_regex::PasoRegex;
-use crate::repeticion::Repeticion;
-
-///Caracteres especiales que se utilizan en las expresiones regulares.
-const CORCHETE_ABIERTO: char = '[';
-const CORCHETE_CERRADO: char = ']';
-const LLAVE_ABIERTA: char = '{';
-const LLAVE_CERRADA: char = '}';
-const ASTERISCO: char = '*';
-const INTERROGACION: char = '?';
-const MAS: char = '+';
-const PUNTO: char = '.';
-const BARRA: char = '\\';
-const DOLAR: char = '$';
-const CARET: char = '^';
-const INDICADOR_CLASE: char = ':';
-const SEPARADOR_RANGO: char = '-';
-const FUNCION_OR: char = '|';
+use crate::evaluador::Evaluador;
+use crate::maquina_pike::MaquinaPike;
+use crate::programa::Programa;
+use crate::traductor::traducir;
 
 ///Representa una expresión regular que se puede evaluar en una cadena de texto.
-/// Contiene una lista de pasos que se deben cumplir para que la expresión regular sea válida.
+/// Contiene el programa en el que se compilan los pasos que se deben cumplir para que la
+/// expresión regular sea válida y el nombre de cada grupo de captura (None si el grupo no
+/// tiene nombre), donde la posición 0 corresponde a la coincidencia completa.
 pub struct Regex {
-    pasos: Vec<PasoRegex>,
-}
-
-///Obtiene el contenido de un corchete, si es que lo hay.
-fn obtener_auxiliar(chars_iter: &mut Chars<'_>) -> (Vec<char>, bool, bool) {
-    let mut cantidad_corchetes = 1;
-    let mut hay_clase = false;
-    let mut es_negado = false;
-    let mut auxiliar: Vec<char> = Vec::new();
-
-    for c in chars_iter.by_ref() {
-        match c {
-            CORCHETE_CERRADO if cantidad_corchetes == 2 || c == CORCHETE_CERRADO && !hay_clase => {
-                break
-            }
-            CORCHETE_CERRADO => cantidad_corchetes += 1,
-            CARET => es_negado = true,
-            INDICADOR_CLASE => continue,
-            CORCHETE_ABIERTO => hay_clase = true,
-            _ => auxiliar.push(c),
-        }
-    }
-
-    (auxiliar, hay_clase, es_negado)
-}
-
-///Determina el contenido de un corchete.
-fn determinar_contenido_a_evaluar(auxiliar: Vec<char>) -> Result<HashSet<char>, Error> {
-    let mut contenido: HashSet<char> = HashSet::new();
-
-    for i in 0..auxiliar.len() {
-        if auxiliar[i] == SEPARADOR_RANGO {
-            if let (Some(inicio), Some(fin)) = (auxiliar.get(i - 1), auxiliar.get(i + 1)) {
-                contenido.extend(*inicio..=*fin);
-            }
-        } else {
-            contenido.insert(auxiliar[i]);
-        }
-    }
-    Ok(contenido)
-}
-
-///Obtiene la clase de caracter que se debe evaluar.
-/// - Si la clase de caracter es una de las predefinidas, se devuelve la clase de caracter correspondiente.
-/// - Si la clase de caracter no es predefinida, se determina el contenido de la clase de caracter.
-/// - Si no se puede determinar el contenido de la clase de caracter, se devuelve un error.
-fn conseguir_lista(chars_iter: &mut Chars<'_>) -> Result<ClaseChar, Error> {
-    let (auxiliar, hay_clase, es_negado) = obtener_auxiliar(chars_iter);
-
-    if hay_clase {
-        let class: String = auxiliar.iter().collect();
-
-        match class.to_string().as_str() {
-            "alpha" => return Ok(ClaseChar::Alpha(es_negado)),
-            "alnum" => return Ok(ClaseChar::Alnum(es_negado)),
-            "digit" => return Ok(ClaseChar::Digit(es_negado)),
-            "lower" => return Ok(ClaseChar::Lower(es_negado)),
-            "upper" => return Ok(ClaseChar::Upper(es_negado)),
-            "space" => return Ok(ClaseChar::Space(es_negado)),
-            "punct" => return Ok(ClaseChar::Punct(es_negado)),
-            _ => {}
-        }
-    }
-
-    let contenido = determinar_contenido_a_evaluar(auxiliar);
-
-    match contenido {
-        Ok(contenido) => Ok(ClaseChar::Simple(contenido, es_negado)),
-        Err(error) => Err(error),
-    }
-}
-
-///Determina si el caracter anterior es un comodín.
-///Para verificar que la expresión regular no comience con una repetición.
-fn no_hay_anterior(anterior: &mut PasoRegex) -> bool {
-    anterior.caracter_interno == Caracter::Comodin && anterior.repeticiones == Repeticion::Alguna
-}
-
-fn fabricar_paso_punto() -> Result<Option<PasoRegex>, Error> {
-    Ok(Some(PasoRegex {
-        repeticiones: Repeticion::Exacta(1),
-        caracter_interno: Caracter::Comodin,
-    }))
-}
-
-fn fabricar_paso_literal(c: char) -> Result<Option<PasoRegex>, Error> {
-    Ok(Some(PasoRegex {
-        repeticiones: Repeticion::Exacta(1),
-        caracter_interno: Caracter::Literal(c),
-    }))
-}
-
-fn fabricar_paso_llave(
-    steps: &mut [PasoRegex],
-    chars_iter: &mut Chars<'_>,
-) -> Result<Option<PasoRegex>, Error> {
-    if let Some(ultimo) = steps.last_mut() {
-        let mut contenido: Vec<char> = Vec::new();
-        let mut rangos: Vec<usize> = Vec::new();
-        for c in chars_iter.by_ref() {
-            if c == ',' {
-                contenido.push(c);
-            } else if c == LLAVE_CERRADA {
-                break;
-            } else {
-                contenido.push(c);
-                match c.to_string().parse::<usize>() {
-                    Ok(cant) => rangos.push(cant),
-                    Err(_) => return Err(Error::ErrorEnLlaves),
-                }
-            }
-        }
-
-        if contenido.len() >= 2 {
-            if contenido[0] == ',' {
-                ultimo.repeticiones = Repeticion::Rango {
-                    min: None,
-                    max: Some(rangos[0]),
-                };
-            } else if contenido[contenido.len() - 1] == ',' {
-                ultimo.repeticiones = Repeticion::Rango {
-                    min: Some(rangos[0]),
-                    max: None,
-                };
-            } else {
-                if rangos[0] > rangos[1] {
-                    return Err(Error::ErrorEnLlaves);
-                }
-                ultimo.repeticiones = Repeticion::Rango {
-                    min: Some(rangos[0]),
-                    max: Some(rangos[1]),
-                };
-            }
-        } else if contenido.len() == 1 && contenido[0].is_ascii_digit() {
-            ultimo.repeticiones = Repeticion::Exacta(rangos[0]);
-        } else {
-            return Err(Error::ErrorEnLlaves);
-        }
-    }
-    Ok(None)
-}
-
-fn fabricar_paso_corchete(chars_iter: &mut Chars<'_>) -> Result<Option<PasoRegex>, Error> {
-    match conseguir_lista(chars_iter) {
-        Ok(contenido) => Ok(Some(PasoRegex {
-            repeticiones: Repeticion::Exacta(1),
-            caracter_interno: Caracter::Serie(contenido),
-        })),
-        Err(error) => Err(error),
-    }
-}
-
-fn fabricar_paso_interrogacion(steps: &mut [PasoRegex]) -> Result<Option<PasoRegex>, Error> {
-    if let Some(ultimo) = steps.last_mut() {
-        if no_hay_anterior(ultimo) {
-            return Err(Error::ErrorEnRepeticion);
-        } else {
-            ultimo.repeticiones = Repeticion::Rango {
-                min: Some(0),
-                max: Some(1),
-            };
-        }
-    }
-    Ok(None)
-}
-
-fn fabricar_paso_mas(steps: &mut [PasoRegex]) -> Result<Option<PasoRegex>, Error> {
-    if let Some(ultimo) = steps.last_mut() {
-        if no_hay_anterior(ultimo) {
-            return Err(Error::ErrorEnRepeticion);
-        } else {
-            ultimo.repeticiones = Repeticion::Rango {
-                min: Some(1),
-                max: None,
-            };
-        }
-    }
-    Ok(None)
-}
-
-fn fabricar_paso_asterisco(steps: &mut [PasoRegex]) -> Result<Option<PasoRegex>, Error> {
-    if let Some(ultimo) = steps.last_mut() {
-        if no_hay_anterior(ultimo) {
-            return Err(Error::ErrorEnRepeticion);
-        } else {
-            ultimo.repeticiones = Repeticion::Alguna;
-        }
-    }
-    Ok(None)
-}
-
-fn fabricar_paso_barra(chars_iter: &mut Chars<'_>) -> Result<Option<PasoRegex>, Error> {
-    match chars_iter.next() {
-        Some(literal) => Ok(Some(PasoRegex {
-            repeticiones: Repeticion::Exacta(1),
-            caracter_interno: Caracter::Literal(literal),
-        })),
-        None => Err(Error::CaracterNoProcesable),
-    }
-}
-
-fn fabricar_paso_dolar() -> Result<Option<PasoRegex>, Error> {
-    Ok(Some(PasoRegex {
-        repeticiones: Repeticion::Exacta(1),
-        caracter_interno: Caracter::Dolar,
-    }))
-}
-
-fn fabricar_paso_caracter(
-    c: char,
-    steps: &mut [PasoRegex],
-    chars_iter: &mut Chars<'_>,
-) -> Result<Option<PasoRegex>, Error> {
-    match c {
-        PUNTO => fabricar_paso_punto(),
-        'a'..='z' | 'A'..='Z' | '0'..='9' | ' ' => fabricar_paso_literal(c),
-        LLAVE_ABIERTA => fabricar_paso_llave(steps, chars_iter),
-        CORCHETE_ABIERTO => fabricar_paso_corchete(chars_iter),
-        INTERROGACION => fabricar_paso_interrogacion(steps),
-        ASTERISCO => fabricar_paso_asterisco(steps),
-        MAS => fabricar_paso_mas(steps),
-        BARRA => fabricar_paso_barra(chars_iter),
-        DOLAR => fabricar_paso_dolar(),
-        CARET => Ok(None),
-        FUNCION_OR => Ok(None),
-        _ => Err(Error::CaracterNoProcesable),
-    }
-}
-
-///Agrega los pasos a la expresión regular.
-/// - Si el caracter es un punto, se agrega un paso con un comodín.
-/// - Si el caracter es un literal, se agrega un paso con el literal.
-/// - Si el caracter es una llave abierta, se obtiene el contenido de la llave y se agrega un paso con la cantidad de repeticiones
-/// y según si se trata de una repetición exacta, con solo mínimo, con solo máximo o ambas.
-/// - Si el caracter es un corchete abierto, se obtiene el contenido del corchete y se agrega un paso con la clase de caracteres.
-/// - Si el caracter es un asterisco, se agrega un paso con Alguna cantidad de repeticiones.
-/// - Si el caracter es un signo de interrogación, se agrega un paso con la cantidad de repeticiones (0 o 1 vez).
-/// - Si el caracter es un signo de más, se agrega un paso con la cantidad de repeticiones (1 o más).
-/// - Si el caracter es una barra, se obtiene el siguiente caracter y se agrega un paso con el literal.
-/// - Si el caracter es un dolar, se agrega un paso con un dolar.
-/// - Si el caracter es un caret, no se agrega un paso.
-/// - Si el caracter es una función OR, no se agrega un paso.
-/// - Si el caracter no es procesable, se devuelve un error.
-pub fn agregar_pasos(
-    pasos: &mut Vec<PasoRegex>,
-    chars_iter: &mut Chars<'_>,
-) -> Result<Vec<PasoRegex>, Error> {
-    while let Some(c) = chars_iter.next() {
-        if let Some(paso) = fabricar_paso_caracter(c, pasos, chars_iter)? {
-            pasos.push(paso);
-        }
-    }
-    Ok(pasos.to_vec())
-}
-
-///Determina si se debe agregar un paso con un comodín al principio de la expresión regular
-///(como un .*), si esta no comienza con un CARET ^.
-fn definir_uso_de_caret(expresion: &str, pasos: &mut Vec<PasoRegex>) {
-    if !expresion.starts_with(CARET) {
-        let paso = Some(PasoRegex {
-            repeticiones: Repeticion::Alguna,
-            caracter_interno: Caracter::Comodin,
-        });
-        if let Some(p) = paso {
-            pasos.push(p);
-        }
-    }
-}
-
-///Verifica si la expresión regular está escrita correctamente.
-///Determina si las llaves y los corchetes se abren y se cierran
-///como corresponde. En cada caso devuelve un error explicativo.
-///Además, verifica que la función OR no esté dentro de llaves o corchetes.
-fn expresion_escrita_correctamente(expresion: &str) -> Result<(), Error> {
-    let iter = expresion.chars();
-    let mut cont_llaves = 0;
-    let mut cont_corchetes = 0;
-    for c in iter {
-        match c {
-            LLAVE_ABIERTA => cont_llaves += 1,
-            LLAVE_CERRADA => cont_llaves -= 1,
-            CORCHETE_ABIERTO => cont_corchetes += 1,
-            CORCHETE_CERRADO => cont_corchetes -= 1,
-            FUNCION_OR => {
-                if cont_llaves != 0 || cont_corchetes != 0 {
-                    return Err(Error::ErrorEnFuncionOR);
-                }
-            }
-            _ => {}
-        }
-    }
-    if cont_llaves != 0 {
-        return Err(Error::ErrorEnLlaves);
-    }
-    if cont_corchetes != 0 {
-        return Err(Error::ErrorEnCorchetes);
-    }
-    Ok(())
+    programa: Programa,
+    nombres_capturas: Arc<Vec<Option<String>>>,
 }
 
 impl Regex {
     ///Verifica si una expresión regular es válida para una línea de texto.
     /// - Si la expresión regular está escrita correctamente, se evalúa si la línea cumple con la expresión regular.
-    /// En caso de tener una función OR, se evalúa si alguna de las expresiones es válida.
     /// - Si la expresión regular no está escrita correctamente, se devuelve un error.
     pub fn es_valida_general(expresion_completa: &str, linea: &str) -> Result<bool, Error> {
-        let mut valida = false;
-
-        match expresion_escrita_correctamente(expresion_completa) {
-            Ok(_) => {
-                let expresiones_a_evaluar: Vec<&str> = expresion_completa.split('|').collect();
-
-                for exp in expresiones_a_evaluar {
-                    let regex = match Regex::new(exp) {
-                        Ok(regex) => regex,
-                        Err(err) => return Err(err),
-                    };
-                    if regex.es_valida(linea)? {
-                        valida = true;
-                        break;
-                    }
-                }
-                Ok(valida)
-            }
-            Err(error) => Err(error),
-        }
+        Regex::new(expresion_completa)
+            .map_err(|error| error.tipo())?
+            .es_valida(linea)
     }
 
     ///Crea una nueva expresión regular a partir de una cadena de texto.
-    ///Teniendo en cuenta si comienza con un CARET ^ o no.
-    pub fn new(expresion: &str) -> Result<Self, Error> {
-        let mut pasos: Vec<PasoRegex> = Vec::new();
-        let mut chars_iter = expresion.chars();
-
-        definir_uso_de_caret(expresion, &mut pasos);
-
-        let pasos: Vec<PasoRegex> = agregar_pasos(&mut pasos, &mut chars_iter)?;
-        Ok(Regex { pasos })
-    }
-
-    ///Procesa "alguna" repeticion.
-    fn procesar_alguna(
-        paso: &PasoRegex,
-        linea: &str,
-        index: &mut usize,
-        pila: &mut Vec<PasoEvaluado>,
-    ) {
-        let mut sigo_avanzando = true;
-        while sigo_avanzando {
-            let avance = paso.caracter_interno.coincide(&linea[*index..]);
-            if avance != 0 {
-                *index += avance;
-                pila.push(PasoEvaluado {
-                    paso: paso.clone(),
-                    tam_matcheo: avance,
-                    backtrackeable: true,
-                })
-            } else {
-                sigo_avanzando = false;
-            }
-        }
+    ///Las alternativas separadas por la función OR quedan dentro de la misma expresión.
+    pub fn new(expresion: &str) -> Result<Self, ErrorEnExpresion> {
+        Regex::con_configuracion(expresion, &Configuracion::default())
     }
 
-    ///Procesa una repetición exacta.
-    fn procesar_exacta(
-        cola: &mut VecDeque<PasoRegex>,
-        pila: &mut Vec<PasoEvaluado>,
-        index: &mut usize,
-        paso: PasoRegex,
-        linea: &str,
-        n: usize,
-    ) -> Result<bool, Error> {
-        let mut tam_coincidencia = 0;
-        for _ in 0..n {
-            let avance = paso.caracter_interno.coincide(&linea[*index..]);
-            if avance == 0 {
-                if let Some(size) = backtrack(paso.clone(), pila, cola) {
-                    *index -= size;
-                    return Ok(true);
-                } else {
-                    return Ok(false);
-                }
-            } else {
-                tam_coincidencia += avance;
-                *index += avance;
-            }
-        }
-        pila.push(PasoEvaluado {
-            paso,
-            tam_matcheo: tam_coincidencia,
-            backtrackeable: false,
-        });
-        Ok(true)
-    }
-
-    ///Procesa una repetición en un rango de min a max.
-    fn procesar_rango(
-        cola: &mut VecDeque<PasoRegex>,
-        pila: &mut Vec<PasoEvaluado>,
-        index: &mut usize,
-        paso: PasoRegex,
-        linea: &str,
-        min: Option<usize>,
-        max: Option<usize>,
-    ) -> Result<bool, Error> {
-        let min = min.unwrap_or(0);
-
-        let max = match max {
-            Some(max) => max,
-            None => linea.len() - *index,
+    ///Crea una nueva expresión regular a partir de una cadena de texto escrita en el dialecto indicado.
+    pub fn con_dialecto(expresion: &str, dialecto: Dialecto) -> Result<Self, ErrorEnExpresion> {
+        let configuracion = Configuracion {
+            dialecto,
+            ..Configuracion::default()
         };
-        let mut matches = 0;
-        let mut sigo_avanzando = true;
-        while sigo_avanzando && *index < linea.len() {
-            let avance = paso.caracter_interno.coincide(&linea[*index..]);
-            if avance != 0 {
-                matches += 1;
-                let back = matches >= min && matches <= max;
-                *index += avance;
-                pila.push(PasoEvaluado {
-                    paso: PasoRegex {
-                        caracter_interno: paso.caracter_interno.clone(),
-                        repeticiones: Repeticion::Exacta(1),
-                    },
-                    tam_matcheo: avance,
-                    backtrackeable: back,
-                });
-                if matches == max {
-                    sigo_avanzando = false;
-                }
-            } else {
-                sigo_avanzando = false;
-            }
-        }
-
-        if matches < min {
-            if let Some(size) = backtrack(paso.clone(), pila, cola) {
-                *index -= size;
-                return Ok(true);
-            } else {
-                return Ok(false);
-            }
-        }
-
-        Ok(true)
+        Regex::con_configuracion(expresion, &configuracion)
+    }
+
+    ///Crea una nueva expresión regular a partir de una cadena de texto,
+    ///con las opciones indicadas en la configuración.
+    ///Primero se analiza la expresión para obtener su árbol de sintaxis, y luego se traduce
+    ///a los pasos que se evalúan según la configuración, que se compilan a un programa.
+    /// - Si la expresión no está escrita correctamente, se devuelve un error que indica
+    ///   en qué parte de la expresión está el problema.
+    /// - Si el programa es demasiado grande, se devuelve un error que señala toda la expresión.
+    pub fn con_configuracion(
+        expresion: &str,
+        configuracion: &Configuracion,
+    ) -> Result<Self, ErrorEnExpresion> {
+        let mut analizador = Analizador::new(expresion, configuracion);
+        let alternativas = analizador.analizar()?;
+        let pasos = traducir(&alternativas, configuracion);
+        let nombres_capturas = analizador.nombres_capturas();
+        let programa = Programa::compilar(&pasos, nombres_capturas.len() - 1).ok_or_else(|| {
+            ErrorEnExpresion::new(
+                Error::ExpresionDemasiadoGrande,
+                expresion,
+                0,
+                expresion.len(),
+            )
+        })?;
+        Ok(Regex {
+            programa,
+            nombres_capturas: Arc::new(nombres_capturas),
+        })
     }
 
     ///Verifica si una expresión regular es válida para una línea de texto,
     ///es el "validador" de la expresión regular.
-    ///Según el tipo de repetición, se busca en la línea de texto la
-    ///coincidencia. Si no se encuentra, se evalúa si se puede hacer un backtrack.
-    pub fn es_valida(self, linea: &str) -> Result<bool, Error> {
-        if !linea.is_ascii() {
-            return Err(Error::FormatoDeLineaNoASCII);
-        }
-        let mut cola: VecDeque<PasoRegex> = VecDeque::from(self.pasos);
-        let mut pila: Vec<PasoEvaluado> = Vec::new();
-        let mut index = 0;
-
-        while let Some(paso) = cola.pop_front() {
-            match paso.repeticiones {
-                Repeticion::Exacta(n) => {
-                    if !Self::procesar_exacta(&mut cola, &mut pila, &mut index, paso, linea, n)? {
-                        return Ok(false);
-                    }
-                }
-                Repeticion::Alguna => Self::procesar_alguna(&paso, linea, &mut index, &mut pila),
-                Repeticion::Rango { min, max } => {
-                    if !Self::procesar_rango(
-                        &mut cola, &mut pila, &mut index, paso, linea, min, max,
-                    )? {
-                        return Ok(false);
-                    }
-                }
-            }
+    ///La línea se recorre por caracteres UTF-8, por lo que puede tener acentos o emojis.
+    ///Devuelve un error si la evaluación con backtracking supera el límite de pasos.
+    pub fn es_valida(&self, linea: &str) -> Result<bool, Error> {
+        Ok(self.buscar(linea, 0)?.is_some())
+    }
+
+    ///Busca la primera coincidencia del programa en el texto que empieza en la posición desde
+    ///o después, y devuelve las posiciones guardadas en cada ranura.
+    /// - Si el programa no necesita backtracking, se evalúa con la máquina de Pike, en tiempo lineal.
+    /// - Si no, por ejemplo si tiene referencias o afirmaciones, se evalúa con backtracking,
+    ///   con una cantidad de pasos limitada.
+    fn buscar(&self, texto: &str, desde: usize) -> Result<Option<Vec<Option<usize>>>, Error> {
+        if self.programa.necesita_backtracking {
+            Evaluador::new(&self.programa, texto).buscar(desde)
+        } else {
+            Ok(MaquinaPike::new(&self.programa, texto).buscar(desde))
         }
-        Ok(true)
     }
-}
-
-///Realiza un backtrack en la expresión regular.
-fn backtrack(
-    actual: PasoRegex,
-    evaluados: &mut Vec<PasoEvaluado>,
-    siguiente: &mut VecDeque<PasoRegex>,
-) -> Option<usize> {
-    let mut back_size = 0;
-
-    siguiente.push_front(actual);
 
-    while let Some(paso_ev) = evaluados.pop() {
-        back_size += paso_ev.tam_matcheo;
-        if paso_ev.backtrackeable {
-            return Some(back_size);
-        } else {
-            siguiente.push_front(paso_ev.paso);
+    ///Busca la primera coincidencia de la expresión regular en el texto que empieza
+    ///en la posición desde o después, y devuelve lo capturado por cada grupo.
+    fn primera_coincidencia<'t>(
+        &self,
+        texto: &'t str,
+        desde: usize,
+    ) -> Result<Option<Captures<'t>>, Error> {
+        let Some(ranuras) = self.buscar(texto, desde)? else {
+            return Ok(None);
+        };
+        let posiciones = (0..self.nombres_capturas.len())
+            .map(|captura| ranuras[2 * captura].zip(ranuras[2 * captura + 1]))
+            .collect();
+        let nombres = Arc::clone(&self.nombres_capturas);
+        Ok(Some(Captures::new(texto, posiciones, nombres)))
+    }
+
+    ///Busca la primera coincidencia de la expresión regular en el texto y devuelve
+    ///lo capturado por cada grupo, o None si no hay coincidencia.
+    ///Las coincidencias que empiezan antes en el texto tienen prioridad.
+    ///Devuelve un error si la evaluación con backtracking supera el límite de pasos.
+    pub fn captures<'t>(&self, texto: &'t str) -> Result<Option<Captures<'t>>, Error> {
+        self.primera_coincidencia(texto, 0)
+    }
+
+    ///Devuelve la posición en bytes (inicio, fin) de cada coincidencia de la expresión regular
+    ///en el texto, en orden y sin que se superpongan. Después de una coincidencia vacía,
+    ///la búsqueda sigue desde el caracter siguiente.
+    ///Devuelve un error si la evaluación con backtracking supera el límite de pasos.
+    pub fn coincidencias(&self, texto: &str) -> Result<Vec<(usize, usize)>, Error> {
+        let mut coincidencias = Vec::new();
+        let mut desde = 0;
+        while let Some((inicio, fin)) = self
+            .primera_coincidencia(texto, desde)?
+            .and_then(|capturas| capturas.span(0))
+        {
+            coincidencias.push((inicio, fin));
+            desde = match texto[fin..].chars().next() {
+                _ if fin > inicio => fin,
+                Some(c) => fin + c.len_utf8(),
+                None => break,
+            };
         }
+        Ok(coincidencias)
+    }
+
+    ///Devuelve el nombre de cada grupo de captura, en orden, comenzando por la
+    ///coincidencia completa. Los grupos sin nombre aparecen como None.
+    pub fn capture_names(&self) -> impl Iterator<Item = Option<&str>> {
+        self.nombres_capturas.iter().map(|nombre| nombre.as_deref())
     }
-    None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ast::TipoDeNodo;
+    use crate::repeticion::Repeticion;
 
     #[test]
     fn test01_literales() {
         let regex = Regex::new("abcd");
-        assert_eq!(regex.unwrap().es_valida("abcdefg").unwrap(), true);
+        assert!(regex.unwrap().es_valida("abcdefg").unwrap());
     }
 
     #[test]
     fn test02_literales() {
         let regex = Regex::new("^abcd");
-        assert_eq!(regex.unwrap().es_valida("abcdefg").unwrap(), true);
+        assert!(regex.unwrap().es_valida("abcdefg").unwrap());
     }
 
     #[test]
     fn test03_literales() {
         let regex = Regex::new("^abcd");
-        assert_eq!(regex.unwrap().es_valida("ab abcdefg").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("ab abcdefg").unwrap());
     }
 
     #[test]
     fn test04_literales() {
         let regex = Regex::new("abcd");
-        assert_eq!(regex.unwrap().es_valida("efgabcd").unwrap(), true);
+        assert!(regex.unwrap().es_valida("efgabcd").unwrap());
     }
 
     #[test]
     fn test05_literales() {
         let regex = Regex::new("abcd");
-        assert_eq!(regex.unwrap().es_valida("abcefg").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("abcefg").unwrap());
     }
 
     #[test]
     fn test06_punto() {
         let regex = Regex::new("ab.cd");
-        assert_eq!(regex.unwrap().es_valida("ab0cd").unwrap(), true);
+        assert!(regex.unwrap().es_valida("ab0cd").unwrap());
     }
 
     #[test]
     fn test07_punto() {
         let regex = Regex::new("ab.cd");
-        assert_eq!(regex.unwrap().es_valida("abcd").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("abcd").unwrap());
     }
 
     #[test]
     fn test08_regex_con_asterisk() {
         let regex = Regex::new("ab*c");
-        assert_eq!(regex.unwrap().es_valida("abbbbbbcd").unwrap(), true);
+        assert!(regex.unwrap().es_valida("abbbbbbcd").unwrap());
     }
 
     #[test]
     fn test08_punto_asterisco() {
         let regex = Regex::new("ab.*cd");
-        assert_eq!(regex.unwrap().es_valida("abcd").unwrap(), true);
+        assert!(regex.unwrap().es_valida("abcd").unwrap());
     }
 
     #[test]
     fn test09_punto_asterisco() {
         let regex = Regex::new("ab.*cd");
-        assert_eq!(regex.unwrap().es_valida("abaaaaaacd").unwrap(), true);
+        assert!(regex.unwrap().es_valida("abaaaaaacd").unwrap());
     }
 
     #[test]
     fn test10_corchete() {
         let regex = Regex::new("a[bc]d");
-        assert_eq!(regex.unwrap().es_valida("abd").unwrap(), true);
+        assert!(regex.unwrap().es_valida("abd").unwrap());
     }
 
     #[test]
     fn test11_corchete() {
         let regex = Regex::new("a[bc]d");
-        assert_eq!(regex.unwrap().es_valida("acd").unwrap(), true);
+        assert!(regex.unwrap().es_valida("acd").unwrap());
     }
 
     #[test]
     fn test12_corchete() {
         let regex = Regex::new("a[bc]d");
-        assert_eq!(regex.unwrap().es_valida("afd").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("afd").unwrap());
     }
 
     #[test]
     fn test13_barra() {
         let regex = Regex::new("a\\*");
-        assert_eq!(regex.unwrap().es_valida("a*cds").unwrap(), true);
+        assert!(regex.unwrap().es_valida("a*cds").unwrap());
     }
 
     #[test]
     fn test14_mas() {
         let regex = Regex::new("hola+");
-        assert_eq!(regex.unwrap().es_valida("holaa").unwrap(), true);
+        assert!(regex.unwrap().es_valida("holaa").unwrap());
     }
 
     #[test]
     fn test15_mas() {
         let regex = Regex::new("hola+");
-        assert_eq!(regex.unwrap().es_valida("hol").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("hol").unwrap());
     }
 
     #[test]
     fn test16_interrogacion() {
         let regex = Regex::new("holi?s");
-        assert_eq!(regex.unwrap().es_valida("holis").unwrap(), true);
+        assert!(regex.unwrap().es_valida("holis").unwrap());
     }
 
     #[test]
     fn test17_interrogacion() {
         let regex = Regex::new("holi?s");
-        assert_eq!(regex.unwrap().es_valida("hols").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hols").unwrap());
     }
 
     #[test]
     fn test18_interrogacion() {
         let regex = Regex::new("hola?");
-        assert_eq!(regex.unwrap().es_valida("holaaaaa").unwrap(), true);
+        assert!(regex.unwrap().es_valida("holaaaaa").unwrap());
     }
 
     #[test]
     fn test19_llave_exacto() {
         let regex = Regex::new("a{2}");
-        assert_eq!(regex.unwrap().es_valida("a").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("a").unwrap());
     }
 
     #[test]
     fn test20_llave_exacto() {
         let regex = Regex::new("ba{2}");
-        assert_eq!(regex.unwrap().es_valida("baa").unwrap(), true);
+        assert!(regex.unwrap().es_valida("baa").unwrap());
     }
 
     #[test]
     fn test21_llave_exacto() {
         let regex = Regex::new("ba{2,3}c");
-        assert_eq!(regex.unwrap().es_valida("bac baac baaac").unwrap(), true);
+        assert!(regex.unwrap().es_valida("bac baac baaac").unwrap());
     }
 
     #[test]
     fn test22_llave_minimo() {
         let regex = Regex::new("ba{2,}c");
-        assert_eq!(regex.unwrap().es_valida("baaaac").unwrap(), true);
+        assert!(regex.unwrap().es_valida("baaaac").unwrap());
     }
 
     #[test]
     fn test23_llave_minimo() {
         let regex = Regex::new("ba{2,}c");
-        assert_eq!(regex.unwrap().es_valida("bac").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("bac").unwrap());
     }
 
     #[test]
     fn test24_llave_rango() {
         let regex = Regex::new("ba{5,8}c");
-        assert_eq!(regex.unwrap().es_valida("baaaaaac").unwrap(), true);
+        assert!(regex.unwrap().es_valida("baaaaaac").unwrap());
     }
 
     #[test]
     fn test25_llave_rango() {
         let regex = Regex::new("ba{5,8}c");
-        assert_eq!(regex.unwrap().es_valida("baaaac").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("baaaac").unwrap());
     }
 
     #[test]
     fn test26_llave_rango() {
         let regex = Regex::new("ba{5,8}c");
-        assert_eq!(regex.unwrap().es_valida("baaaaaaaaac").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("baaaaaaaaac").unwrap());
     }
 
     #[test]
     fn test27_llave_maximo() {
         let regex = Regex::new("ba{,8}c");
-        assert_eq!(regex.unwrap().es_valida("baaaaaac").unwrap(), true);
+        assert!(regex.unwrap().es_valida("baaaaaac").unwrap());
     }
 
     #[test]
     fn test28_llave_maximo() {
         let regex = Regex::new("ba{,8}c");
-        assert_eq!(
-            regex.unwrap().es_valida("baaaaaaaaaaaaaaaac").unwrap(),
-            false
-        );
+        assert!(!regex.unwrap().es_valida("baaaaaaaaaaaaaaaac").unwrap());
     }
 
     #[test]
     fn test29_corchete_literal() {
         let regex = Regex::new("ho[lmn]a");
-        assert_eq!(regex.unwrap().es_valida("hola").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hola").unwrap());
     }
 
     #[test]
     fn test30_corchete_literal() {
         let regex = Regex::new("ho[lmn]a");
-        assert_eq!(regex.unwrap().es_valida("hoka").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("hoka").unwrap());
     }
 
     #[test]
     fn test31_corchete_rango() {
         let regex = Regex::new("ho[i-m]a");
-        assert_eq!(regex.unwrap().es_valida("hola").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hola").unwrap());
     }
 
     #[test]
     fn test32_corchete_rango() {
         let regex = Regex::new("ho[i-m]a");
-        assert_eq!(regex.unwrap().es_valida("hosa").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("hosa").unwrap());
     }
 
     #[test]
     fn test33_corchete_rango() {
         let regex = Regex::new("ho[a-dA-Cx-z]");
-        assert_eq!(regex.unwrap().es_valida("hoAa").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hoAa").unwrap());
     }
 
     #[test]
     fn test34_corchete_rango() {
         let regex = Regex::new("ho[a-dA-Cx-z]");
-        assert_eq!(regex.unwrap().es_valida("hoXa").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("hoXa").unwrap());
     }
 
     #[test]
     fn test35_corchete_rango_negado() {
         let regex = Regex::new("ho[^a-dA-Cx-z]");
-        assert_eq!(regex.unwrap().es_valida("hoXa").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hoXa").unwrap());
     }
 
     #[test]
     fn test36_corchete_rango_negado() {
         let regex = Regex::new("ho[^a-dA-Cx-z]");
-        assert_eq!(regex.unwrap().es_valida("hoxa").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("hoxa").unwrap());
     }
 
     #[test]
     fn test37_corchete_interrogacion() {
         let regex = Regex::new("ho[a-dA-Cx-z]?a");
-        assert_eq!(regex.unwrap().es_valida("hoddda").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("hoddda").unwrap());
     }
 
     #[test]
     fn test38_corchete_interrogacion() {
         let regex = Regex::new("ho[a-dA-Cx-z]?a");
-        assert_eq!(regex.unwrap().es_valida("esa hoa").unwrap(), true);
+        assert!(regex.unwrap().es_valida("esa hoa").unwrap());
     }
 
     #[test]
     fn test39_corchete_interrogacion() {
         let regex = Regex::new("ho[d-g]?a");
-        assert_eq!(regex.unwrap().es_valida("hoea").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hoea").unwrap());
     }
 
     #[test]
     fn test40_corchete_mas() {
         let regex = Regex::new("ho[a-dA-Cx-z]+a");
-        assert_eq!(regex.unwrap().es_valida("hoE").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("hoE").unwrap());
     }
 
     #[test]
     fn test41_corchete_mas() {
         let regex = Regex::new("ho[a-dA-Cx-z]+a");
-        assert_eq!(regex.unwrap().es_valida("hoAAAAAa").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hoAAAAAa").unwrap());
     }
 
     #[test]
     fn test42_corchete_mas() {
         let regex = Regex::new("ho[a-dA-Cx-z]+a");
-        assert_eq!(regex.unwrap().es_valida("hoxxxAAAAa").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hoxxxAAAAa").unwrap());
     }
 
     #[test]
     fn test43_corchete_llave() {
         let regex = Regex::new("ho[a-dA-Cx-z]{2,4}a");
-        assert_eq!(regex.unwrap().es_valida("hoaE").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("hoaE").unwrap());
     }
 
     #[test]
     fn test44_corchete_llave() {
         let regex = Regex::new("ho[a-dA-Cx-z]{2,4}a");
-        assert_eq!(regex.unwrap().es_valida("hoxxaE").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hoxxaE").unwrap());
     }
 
     #[test]
     fn test45_corchete_llave() {
         let regex = Regex::new("ho[a-dA-Cx-z]{2,4}a");
-        assert_eq!(regex.unwrap().es_valida("hoCCCCCa").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("hoCCCCCa").unwrap());
     }
 
     #[test]
     fn test46_corchete_asterisco() {
         let regex = Regex::new("ho[a-dA-Cx-z]*a");
-        assert_eq!(regex.unwrap().es_valida("hoa").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hoa").unwrap());
     }
 
     #[test]
     fn test47_corchete_asterisco() {
         let regex = Regex::new("ho[a-dA-Cx-z]*a");
-        assert_eq!(regex.unwrap().es_valida("hoAAAa").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hoAAAa").unwrap());
     }
 
     #[test]
     fn test48_corchete_asterisco_negado() {
         let regex = Regex::new("ho[^a-dA-Cx-z]*a");
-        assert_eq!(regex.unwrap().es_valida("hoKa").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hoKa").unwrap());
     }
 
     #[test]
     fn test49_corchete_asterisco_negado() {
         let regex = Regex::new("ho[^a-dA-Cx-z]*a");
-        assert_eq!(regex.unwrap().es_valida("hoa").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hoa").unwrap());
     }
 
     #[test]
     fn test50_clase_alpha() {
         let regex = Regex::new("ho[[:alpha:]]a");
-        assert_eq!(regex.unwrap().es_valida("hola").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hola").unwrap());
     }
 
     #[test]
     fn test51_clase_alpha_negada() {
         let regex = Regex::new("ho[^[:alpha:]]a");
-        assert_eq!(regex.unwrap().es_valida("ho8a").unwrap(), true);
+        assert!(regex.unwrap().es_valida("ho8a").unwrap());
     }
 
     #[test]
     fn test52_clase_alnum() {
         let regex = Regex::new("ho[[:alnum:]]a");
-        assert_eq!(regex.unwrap().es_valida("hoKa").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hoKa").unwrap());
     }
 
     #[test]
     fn test53_clase_alnum() {
         let regex = Regex::new("ho[[:alnum:]]a");
-        assert_eq!(regex.unwrap().es_valida("ho4a").unwrap(), true);
+        assert!(regex.unwrap().es_valida("ho4a").unwrap());
     }
 
     #[test]
     fn test54_clase_alnum_negada() {
         let regex = Regex::new("ho[^[:alnum:]]a");
-        assert_eq!(regex.unwrap().es_valida("ho&a").unwrap(), true);
+        assert!(regex.unwrap().es_valida("ho&a").unwrap());
     }
 
     #[test]
     fn test55_clase_digit() {
         let regex = Regex::new("ho[[:digit:]]a");
-        assert_eq!(regex.unwrap().es_valida("ho2a").unwrap(), true);
+        assert!(regex.unwrap().es_valida("ho2a").unwrap());
     }
 
     #[test]
     fn test56_clase_digit_mas() {
         let regex = Regex::new("ho[[:digit:]]+");
-        assert_eq!(regex.unwrap().es_valida("ho9999999").unwrap(), true);
+        assert!(regex.unwrap().es_valida("ho9999999").unwrap());
     }
 
     #[test]
     fn test57_clase_digit_negada() {
         let regex = Regex::new("ho[^[:digit:]]a");
-        assert_eq!(regex.unwrap().es_valida("hoea").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hoea").unwrap());
     }
 
     #[test]
     fn test58_clase_lower() {
         let regex = Regex::new("ho[[:lower:]]a");
-        assert_eq!(regex.unwrap().es_valida("hoRa").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("hoRa").unwrap());
     }
 
     #[test]
     fn test59_clase_lower() {
         let regex = Regex::new("ho[[:lower:]]a");
-        assert_eq!(regex.unwrap().es_valida("hora").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hora").unwrap());
     }
 
     #[test]
     fn test60_clase_upper() {
         let regex = Regex::new("ho[[:upper:]]a");
-        assert_eq!(regex.unwrap().es_valida("hoRa").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hoRa").unwrap());
     }
 
     #[test]
     fn test61_clase_upper() {
         let regex = Regex::new("ho[[:upper:]]a");
-        assert_eq!(regex.unwrap().es_valida("hola").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("hola").unwrap());
     }
 
     #[test]
     fn test62_clase_space() {
         let regex = Regex::new("ho[[:space:]]a");
-        assert_eq!(regex.unwrap().es_valida("ho a").unwrap(), true);
+        assert!(regex.unwrap().es_valida("ho a").unwrap());
     }
 
     #[test]
     fn test63_clase_space() {
         let regex = Regex::new("ho[[:space:]]a");
-        assert_eq!(regex.unwrap().es_valida("hola").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("hola").unwrap());
     }
 
     #[test]
     fn test64_clase_punct() {
         let regex = Regex::new("ho[[:punct:]]a");
-        assert_eq!(regex.unwrap().es_valida("ho;a").unwrap(), true);
+        assert!(regex.unwrap().es_valida("ho;a").unwrap());
     }
 
     #[test]
     fn test65_clase_punct() {
         let regex = Regex::new("ho[[:punct:]]a");
-        assert_eq!(regex.unwrap().es_valida("ho9a").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("ho9a").unwrap());
     }
 
     #[test]
     fn test66_dolar() {
         let regex = Regex::new("hola$");
-        assert_eq!(regex.unwrap().es_valida("hola").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hola").unwrap());
     }
 
     #[test]
     fn test67_dollar() {
         let regex = Regex::new("hola$");
-        assert_eq!(regex.unwrap().es_valida("el dijo: hola").unwrap(), true);
+        assert!(regex.unwrap().es_valida("el dijo: hola").unwrap());
     }
 
     #[test]
     fn test68_dollar() {
         let regex = Regex::new("hola$");
-        assert_eq!(regex.unwrap().es_valida("el dijo: hol").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("el dijo: hol").unwrap());
     }
 
     #[test]
     fn test69_dolar_caret() {
         let regex = Regex::new("^hola$");
-        assert_eq!(regex.unwrap().es_valida("el dijo: hola").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("el dijo: hola").unwrap());
     }
 
     #[test]
     fn test70_dolar_caret() {
         let regex = Regex::new("^hola$");
-        assert_eq!(regex.unwrap().es_valida("hola me dijo el").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("hola me dijo el").unwrap());
     }
 
     #[test]
     fn test71_dolar_caret() {
         let regex = Regex::new("^hola$");
-        assert_eq!(regex.unwrap().es_valida("hola").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hola").unwrap());
     }
 
     #[test]
     fn test72_combinado() {
         let regex = Regex::new("ba{5,8}.c");
-        assert_eq!(regex.unwrap().es_valida("baaaaaaafc").unwrap(), true);
+        assert!(regex.unwrap().es_valida("baaaaaaafc").unwrap());
     }
 
     #[test]
     fn test73_combinado() {
         let regex = Regex::new("ho[k-o]a.p{2,4}");
-        assert_eq!(regex.unwrap().es_valida("hola3ppp").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hola3ppp").unwrap());
     }
 
     #[test]
     fn test74_combinado() {
         let regex = Regex::new("^ho[[:punct:]]{2}a+");
-        assert_eq!(regex.unwrap().es_valida("ho..aaaaaa").unwrap(), true);
+        assert!(regex.unwrap().es_valida("ho..aaaaaa").unwrap());
     }
 
     #[test]
     fn test75_combinado() {
         let regex = Regex::new("ho[[:punct:]]{2}a+");
-        assert_eq!(regex.unwrap().es_valida("aaaaa ho..aaaaaa").unwrap(), true);
+        assert!(regex.unwrap().es_valida("aaaaa ho..aaaaaa").unwrap());
     }
 
     #[test]
     fn test76_combinado() {
         let regex = Regex::new("[a-kA-G]ho[[:punct:]]*a\\.?");
-        assert_eq!(regex.unwrap().es_valida("Dho;.a.").unwrap(), true);
+        assert!(regex.unwrap().es_valida("Dho;.a.").unwrap());
     }
 
     #[test]
     fn test77_combinado() {
         let regex = Regex::new("^hola [[:alpha:]]+");
-        assert_eq!(regex.unwrap().es_valida("hola como estas").unwrap(), true);
+        assert!(regex.unwrap().es_valida("hola como estas").unwrap());
     }
 
     #[test]
     fn test78_combinado() {
         let regex = Regex::new("^hola [[:alpha:]]+");
-        assert_eq!(
-            regex
-                .unwrap()
-                .es_valida("el me dijo: hola como estas")
-                .unwrap(),
-            false
-        );
+        assert!(!regex
+            .unwrap()
+            .es_valida("el me dijo: hola como estas")
+            .unwrap());
     }
 
     #[test]
     fn test79_combinado() {
         let regex = Regex::new("[[:upper:]]ascal[[:upper:]]ase");
-        assert_eq!(regex.unwrap().es_valida("PascalCase").unwrap(), true);
+        assert!(regex.unwrap().es_valida("PascalCase").unwrap());
     }
 
     #[test]
     fn test80_combinado() {
         let regex = Regex::new("[[:upper:]]ascal[[:upper:]]ase");
-        assert_eq!(regex.unwrap().es_valida("Pascalcase").unwrap(), false);
+        assert!(!regex.unwrap().es_valida("Pascalcase").unwrap());
     }
 
     #[test]
     fn test81_combinacion_general() {
-        assert_eq!(
-            Regex::es_valida_general("[abc]d[[:alpha:]]|k", "hola").unwrap(),
-            false
-        );
+        assert!(!Regex::es_valida_general("[abc]d[[:alpha:]]|k", "hola").unwrap());
     }
 
     #[test]
     fn test82_combinacion_general() {
-        assert_eq!(
-            Regex::es_valida_general("[abc]d[[:alpha:]]|k", "adAk").unwrap(),
-            true
-        );
+        assert!(Regex::es_valida_general("[abc]d[[:alpha:]]|k", "adAk").unwrap());
     }
 
     #[test]
     fn test83_funcion_or() {
-        assert_eq!(Regex::es_valida_general("abc|de+f", "abc").unwrap(), true);
+        assert!(Regex::es_valida_general("abc|de+f", "abc").unwrap());
     }
 
     #[test]
     fn test84_funcion_or() {
+        assert!(Regex::es_valida_general("abc|de+f", "deeeeeeeeeeeeeef").unwrap());
+    }
+
+    #[test]
+    fn test85_funcion_or() {
+        assert!(Regex::es_valida_general("abc|de+f", "abcdeeeeeeeeeeeeeef").unwrap());
+    }
+
+    #[test]
+    fn test86_funcion_or() {
+        assert!(!Regex::es_valida_general("abc|de+f", "abdeeeeeeeeeeeeee").unwrap());
+    }
+    #[test]
+    fn test87_rangos_seguidos() {
+        assert!(
+            Regex::es_valida_general("abc{2,5}d abc{1,4}d", "en medio abcccd abcd fin").unwrap()
+        );
+    }
+
+    #[test]
+    fn test88_rangos_seguidos() {
+        assert!(
+            Regex::es_valida_general("abc{2,5}d abc{0,}d", "en medio abcccccd abd fin").unwrap()
+        );
+    }
+
+    #[test]
+    fn test89_rangos_seguidos() {
+        assert!(
+            Regex::es_valida_general("abc{2,5}d abc{0,}d", "en medio abcccd abcd fin").unwrap()
+        );
+    }
+
+    #[test]
+    fn test90_punto_question() {
+        assert!(!Regex::es_valida_general("ab.?d", "abhhd").unwrap());
+    }
+
+    #[test]
+    fn test91_punto_question() {
+        assert!(Regex::es_valida_general("ab.?d", "hola abcd chau").unwrap());
+    }
+
+    #[test]
+    fn test92_grupo_mas() {
+        let regex = Regex::new("x(ab)+x");
+        assert!(regex.unwrap().es_valida("xxababx").unwrap());
+    }
+
+    #[test]
+    fn test93_grupo_mas() {
+        let regex = Regex::new("^(ab)+$");
+        assert!(!regex.unwrap().es_valida("ababa").unwrap());
+    }
+
+    #[test]
+    fn test94_grupo_llave() {
+        let regex = Regex::new("^(0x[0-9]{2}){2}$");
+        assert!(regex.unwrap().es_valida("0x120x34").unwrap());
+    }
+
+    #[test]
+    fn test95_grupo_asterisco() {
+        let regex = Regex::new("^(0x[0-9]{2})*$");
+        assert!(!regex.unwrap().es_valida("0x120x3").unwrap());
+    }
+
+    #[test]
+    fn test96_grupo_interrogacion() {
+        let regex = Regex::new("^a(bc)?d$");
+        assert!(regex.unwrap().es_valida("ad").unwrap());
+    }
+
+    #[test]
+    fn test97_grupos_anidados() {
+        let regex = Regex::new("^a(b(cd)?e)+f$");
+        assert!(regex.unwrap().es_valida("abebcdebef").unwrap());
+    }
+
+    #[test]
+    fn test98_grupo_backtrack() {
+        let regex = Regex::new("^(a*)*b");
+        assert!(regex.unwrap().es_valida("aaab").unwrap());
+    }
+
+    #[test]
+    fn test99_grupo_backtrack() {
+        let regex = Regex::new("(a.)+ac");
+        assert!(regex.unwrap().es_valida("abababac").unwrap());
+    }
+
+    #[test]
+    fn test100_parentesis_sin_cerrar() {
+        let regex = Regex::new("(ab");
         assert_eq!(
-            Regex::es_valida_general("abc|de+f", "deeeeeeeeeeeeeef").unwrap(),
-            true
+            regex.err().map(|error| error.tipo()),
+            Some(Error::ErrorEnParentesis)
         );
     }
 
     #[test]
-    fn test85_funcion_or() {
+    fn test101_parentesis_sin_abrir() {
+        let regex = Regex::new("ab)");
         assert_eq!(
-            Regex::es_valida_general("abc|de+f", "abcdeeeeeeeeeeeeeef").unwrap(),
-            true
+            regex.err().map(|error| error.tipo()),
+            Some(Error::ErrorEnParentesis)
         );
     }
 
     #[test]
-    fn test86_funcion_or() {
+    fn test102_funcion_or_en_grupo() {
+        let regex = Regex::new("(foo|bar)baz");
+        assert!(regex.unwrap().es_valida("xbarbaz").unwrap());
+    }
+
+    #[test]
+    fn test103_funcion_or_en_grupo() {
+        let regex = Regex::new("(foo|bar)baz");
+        assert!(!regex.unwrap().es_valida("foo bar baz").unwrap());
+    }
+
+    #[test]
+    fn test104_funcion_or_anidada() {
+        let regex = Regex::new("^(a|(b|c)d)+e$");
+        assert!(regex.unwrap().es_valida("abdacde").unwrap());
+    }
+
+    #[test]
+    fn test105_funcion_or_escapada() {
+        let regex = Regex::new("a\\|b");
+        assert!(regex.unwrap().es_valida("a|b").unwrap());
+    }
+
+    #[test]
+    fn test106_funcion_or_escapada() {
+        let regex = Regex::new("a\\|b");
+        assert!(!regex.unwrap().es_valida("b").unwrap());
+    }
+
+    #[test]
+    fn test107_funcion_or_en_corchetes() {
+        let regex = Regex::new("a[|]b");
+        assert!(regex.unwrap().es_valida("a|b").unwrap());
+    }
+
+    #[test]
+    fn test108_funcion_or_anclas() {
+        assert!(Regex::es_valida_general("^ab|cd$", "xcd").unwrap());
+        assert!(Regex::es_valida_general("^ab|cd$", "abx").unwrap());
+        assert!(!Regex::es_valida_general("^ab|cd$", "xabcdx").unwrap());
+    }
+
+    #[test]
+    fn test109_funcion_or_backtrack() {
+        let regex = Regex::new("^(a|ab)c$");
+        assert!(regex.unwrap().es_valida("abc").unwrap());
+    }
+
+    #[test]
+    fn test110_funcion_or_sin_cerrar() {
+        let regex = Regex::new("(a|b");
         assert_eq!(
-            Regex::es_valida_general("abc|de+f", "abdeeeeeeeeeeeeee").unwrap(),
-            false
+            regex.err().map(|error| error.tipo()),
+            Some(Error::ErrorEnParentesis)
         );
     }
+
     #[test]
-    fn test87_rangos_seguidos() {
+    fn test111_repeticion_sin_anterior() {
+        assert_eq!(
+            Regex::new("*a").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnRepeticion)
+        );
         assert_eq!(
-            Regex::es_valida_general("abc{2,5}d abc{1,4}d", "en medio abcccd abcd fin").unwrap(),
-            true
+            Regex::new("a|+b").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnRepeticion)
         );
     }
 
     #[test]
-    fn test88_rangos_seguidos() {
+    fn test112_captures() {
+        let regex = Regex::new("([0-9]+) ([0-9]+)").unwrap();
+        let capturas = regex.captures("del 10 20 al 30 40").unwrap().unwrap();
+        assert_eq!(capturas.len(), 3);
+        assert_eq!(capturas.get(0), Some("10 20"));
+        assert_eq!(capturas.get(1), Some("10"));
+        assert_eq!(capturas.get(2), Some("20"));
+        assert_eq!(capturas.span(2), Some((7, 9)));
+    }
+
+    #[test]
+    fn test113_captures_sin_coincidencia() {
+        let regex = Regex::new("([0-9]+) ([0-9]+)").unwrap();
+        assert_eq!(regex.captures("sin numeros").unwrap(), None);
+    }
+
+    #[test]
+    fn test114_captures_grupo_sin_participar() {
+        let regex = Regex::new("a(b)?(c|(d))").unwrap();
+        let capturas = regex.captures("xac").unwrap().unwrap();
+        assert_eq!(capturas.get(0), Some("ac"));
+        assert_eq!(capturas.get(1), None);
+        assert_eq!(capturas.get(2), Some("c"));
+        assert_eq!(capturas.get(3), None);
+        assert_eq!(capturas.get(4), None);
+    }
+
+    #[test]
+    fn test115_captures_grupo_repetido() {
+        let regex = Regex::new("^(a.)+$").unwrap();
+        let capturas = regex.captures("a1a2a3").unwrap().unwrap();
+        assert_eq!(capturas.get(1), Some("a3"));
+        assert_eq!(capturas.span(1), Some((4, 6)));
+    }
+
+    #[test]
+    fn test116_captures_backtrack() {
+        let regex = Regex::new("(a*)(a*)b").unwrap();
+        let capturas = regex.captures("aaab").unwrap().unwrap();
+        assert_eq!(capturas.get(1), Some("aaa"));
+        assert_eq!(capturas.get(2), Some(""));
+    }
+
+    #[test]
+    fn test117_captures_con_nombre() {
+        let regex = Regex::new("(?P<anio>[0-9]{4}) (?<mes>[0-9]{2})").unwrap();
+        let capturas = regex.captures("fecha 2024 03").unwrap().unwrap();
+        assert_eq!(capturas.name("anio"), Some("2024"));
+        assert_eq!(capturas.name("mes"), Some("03"));
+        assert_eq!(capturas.get(2), Some("03"));
+        assert_eq!(capturas.name("dia"), None);
+    }
+
+    #[test]
+    fn test118_capture_names() {
+        let regex = Regex::new("(?P<anio>a)(b)(?:c)(?<mes>d)").unwrap();
+        let nombres: Vec<Option<&str>> = regex.capture_names().collect();
+        assert_eq!(nombres, vec![None, Some("anio"), None, Some("mes")]);
+    }
+
+    #[test]
+    fn test119_grupo_sin_captura() {
+        let regex = Regex::new("(?:ab)+(c)").unwrap();
+        let capturas = regex.captures("ababc").unwrap().unwrap();
+        assert_eq!(capturas.len(), 2);
+        assert_eq!(capturas.get(0), Some("ababc"));
+        assert_eq!(capturas.get(1), Some("c"));
+    }
+
+    #[test]
+    fn test120_nombre_de_grupo_invalido() {
+        assert_eq!(
+            Regex::new("(?P<1a>x)").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnNombreDeGrupo)
+        );
         assert_eq!(
-            Regex::es_valida_general("abc{2,5}d abc{0,}d", "en medio abcccccd abd fin").unwrap(),
-            true
+            Regex::new("(?<>x)").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnNombreDeGrupo)
+        );
+        assert_eq!(
+            Regex::new("(?<a>x)(?<a>y)").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnNombreDeGrupo)
+        );
+        assert_eq!(
+            Regex::new("(?Px)").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnParentesis)
         );
     }
 
     #[test]
-    fn test89_rangos_seguidos() {
+    fn test121_referencia() {
+        let regex = Regex::new("([a-z]+) \\1").unwrap();
+        assert!(regex.es_valida("dijo que que no").unwrap());
+        assert!(!regex.es_valida("dijo que no").unwrap());
+    }
+
+    #[test]
+    fn test122_referencia_comillas() {
+        let regex = Regex::new("(['\"])[a-z]*\\1").unwrap();
+        let capturas = regex.captures("dijo 'hola\" y 'chau'").unwrap().unwrap();
+        assert_eq!(capturas.get(0), Some("'chau'"));
+    }
+
+    #[test]
+    fn test123_referencia_backtrack() {
+        let regex = Regex::new("^(a+)b\\1$").unwrap();
+        assert!(regex.es_valida("aabaa").unwrap());
+        assert!(!regex.es_valida("aaba").unwrap());
+    }
+
+    #[test]
+    fn test124_referencia_repetida() {
+        let regex = Regex::new("^(ab)\\1{2}$").unwrap();
+        assert!(regex.es_valida("ababab").unwrap());
+        assert!(!regex.es_valida("abab").unwrap());
+    }
+
+    #[test]
+    fn test125_referencia_grupo_vacio() {
+        let regex = Regex::new("^(a*)x\\1+y$").unwrap();
+        assert!(regex.es_valida("xy").unwrap());
+    }
+
+    #[test]
+    fn test126_referencia_invalida() {
         assert_eq!(
-            Regex::es_valida_general("abc{2,5}d abc{0,}d", "en medio abcccd abcd fin").unwrap(),
-            true
+            Regex::new("\\1(a)").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnReferencia)
+        );
+        assert_eq!(
+            Regex::new("(a)\\2").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnReferencia)
         );
     }
 
     #[test]
-    fn test90_punto_question() {
-        assert_eq!(Regex::es_valida_general("ab.?d", "abhhd").unwrap(), false);
+    fn test127_mas_perezoso() {
+        let regex = Regex::new("[<].+?[>]").unwrap();
+        let capturas = regex.captures("<b>negrita</b>").unwrap().unwrap();
+        assert_eq!(capturas.get(0), Some("<b>"));
     }
 
     #[test]
-    fn test91_punto_question() {
+    fn test128_mas_codicioso() {
+        let regex = Regex::new("[<].+[>]").unwrap();
+        let capturas = regex.captures("<b>negrita</b>").unwrap().unwrap();
+        assert_eq!(capturas.get(0), Some("<b>negrita</b>"));
+    }
+
+    #[test]
+    fn test129_asterisco_perezoso() {
+        let regex = Regex::new("a(.*?)b").unwrap();
+        let capturas = regex.captures("axxbxxb").unwrap().unwrap();
+        assert_eq!(capturas.get(1), Some("xx"));
+    }
+
+    #[test]
+    fn test130_interrogacion_perezosa() {
+        let regex = Regex::new("(a??)(a*)").unwrap();
+        let capturas = regex.captures("aaa").unwrap().unwrap();
+        assert_eq!(capturas.get(1), Some(""));
+        assert_eq!(capturas.get(2), Some("aaa"));
+    }
+
+    #[test]
+    fn test131_llave_perezosa() {
+        let regex = Regex::new("a{2,4}?").unwrap();
+        let capturas = regex.captures("aaaaa").unwrap().unwrap();
+        assert_eq!(capturas.get(0), Some("aa"));
+    }
+
+    #[test]
+    fn test132_grupo_perezoso() {
+        let regex = Regex::new("^((ab)+?)(ab)*$").unwrap();
+        let capturas = regex.captures("ababab").unwrap().unwrap();
+        assert_eq!(capturas.get(1), Some("ab"));
+        assert_eq!(capturas.get(3), Some("ab"));
+    }
+
+    #[test]
+    fn test133_perezoso_backtrack() {
+        let regex = Regex::new("^a.*?c$").unwrap();
+        assert!(regex.es_valida("abcbcbc").unwrap());
+        assert!(!regex.es_valida("abcbcb").unwrap());
+    }
+
+    #[test]
+    fn test134_asterisco_posesivo() {
+        let regex = Regex::new("^a*+a").unwrap();
+        assert!(!regex.es_valida("aaaa").unwrap());
+    }
+
+    #[test]
+    fn test135_mas_posesivo() {
+        let regex = Regex::new("^[0-9]++ ").unwrap();
+        assert!(regex.es_valida("123 ").unwrap());
+        assert!(!regex.es_valida("123").unwrap());
+    }
+
+    #[test]
+    fn test136_interrogacion_posesiva() {
+        let regex = Regex::new("^a?+ab").unwrap();
+        assert!(!regex.es_valida("ab").unwrap());
+        assert!(regex.es_valida("aab").unwrap());
+    }
+
+    #[test]
+    fn test137_llave_posesiva() {
+        let regex = Regex::new("^a{1,3}+a").unwrap();
+        assert!(!regex.es_valida("aaa").unwrap());
+        assert!(regex.es_valida("aaaa").unwrap());
+    }
+
+    #[test]
+    fn test138_grupo_atomico() {
+        let regex = Regex::new("^(?>ab|a)c").unwrap();
+        assert!(regex.es_valida("abc").unwrap());
+        assert!(regex.es_valida("ac").unwrap());
+        let regex = Regex::new("^(?>a|ab)c").unwrap();
+        assert!(!regex.es_valida("abc").unwrap());
+    }
+
+    #[test]
+    fn test139_grupo_posesivo() {
+        let regex = Regex::new("^(ab)*+ab").unwrap();
+        assert!(!regex.es_valida("ababab").unwrap());
+        let capturas = Regex::new("(ab)++c")
+            .unwrap()
+            .captures("xababc")
+            .unwrap()
+            .unwrap();
+        assert_eq!(capturas.get(0), Some("ababc"));
+        assert_eq!(capturas.get(1), Some("ab"));
+    }
+
+    #[test]
+    fn test140_posesivo_linea_larga() {
+        let linea = "a".repeat(1000);
+        let regex = Regex::new("(?>a+)+b").unwrap();
+        assert!(!regex.es_valida(&linea).unwrap());
+    }
+
+    #[test]
+    fn test141_anticipacion_positiva() {
+        let regex = Regex::new("[a-z]+(?=[0-9])").unwrap();
+        let capturas = regex.captures("abc def1").unwrap().unwrap();
+        assert_eq!(capturas.get(0), Some("def"));
+    }
+
+    #[test]
+    fn test142_anticipacion_negativa() {
+        let regex = Regex::new("^[0-9]++(?! USD)").unwrap();
+        assert!(!regex.es_valida("100 USD").unwrap());
+        assert!(regex.es_valida("100 ARS").unwrap());
+    }
+
+    #[test]
+    fn test143_retrospeccion_positiva() {
+        let regex = Regex::new("(?<=fn )[a-z]+").unwrap();
+        let capturas = regex.captures("pub fn main").unwrap().unwrap();
+        assert_eq!(capturas.get(0), Some("main"));
+        assert!(!regex.es_valida("let main").unwrap());
+    }
+
+    #[test]
+    fn test144_retrospeccion_negativa() {
+        let regex = Regex::new("(?<!x)ab").unwrap();
+        assert!(!regex.es_valida("xab").unwrap());
+        assert!(regex.es_valida("xab ab").unwrap());
+        assert!(regex.es_valida("ab").unwrap());
+    }
+
+    #[test]
+    fn test145_anticipacion_con_captura() {
+        let regex = Regex::new("a(?=(b+))").unwrap();
+        let capturas = regex.captures("abb").unwrap().unwrap();
+        assert_eq!(capturas.get(0), Some("a"));
+        assert_eq!(capturas.get(1), Some("bb"));
+    }
+
+    #[test]
+    fn test146_afirmaciones_con_alternativas() {
+        let regex = Regex::new("^(?!a|b)[a-z]").unwrap();
+        assert!(!regex.es_valida("b").unwrap());
+        assert!(regex.es_valida("c").unwrap());
+        let regex = Regex::new("(?<=a|bc)d").unwrap();
+        assert!(regex.es_valida("bcd").unwrap());
+        assert!(!regex.es_valida("cd").unwrap());
+    }
+
+    #[test]
+    fn test147_limite_palabra() {
+        let regex = Regex::new("\\bcat\\b").unwrap();
+        assert!(!regex.es_valida("concatenate").unwrap());
+        assert!(regex.es_valida("the cat sat").unwrap());
+        assert!(regex.es_valida("cat").unwrap());
+    }
+
+    #[test]
+    fn test148_no_limite_palabra() {
+        let regex = Regex::new("\\Bcat\\B").unwrap();
+        assert!(regex.es_valida("concatenate").unwrap());
+        assert!(!regex.es_valida("the cat sat").unwrap());
+    }
+
+    #[test]
+    fn test149_inicio_y_fin_palabra() {
+        let regex = Regex::new("\\<ca").unwrap();
+        assert!(regex.es_valida("una casa").unwrap());
+        assert!(!regex.es_valida("toca").unwrap());
+        let regex = Regex::new("ca\\>").unwrap();
+        assert!(regex.es_valida("toca").unwrap());
+        assert!(!regex.es_valida("una casa").unwrap());
+    }
+
+    #[test]
+    fn test150_palabras_repetidas() {
+        let regex = Regex::new("\\b([a-z]+) \\1\\b").unwrap();
+        assert!(regex.es_valida("es es").unwrap());
+        assert!(!regex.es_valida("es esto").unwrap());
+    }
+
+    #[test]
+    fn test151_clases_abreviadas() {
+        let regex = Regex::new("^\\d+\\s\\w+$").unwrap();
+        assert!(regex.es_valida("2024 mi_archivo").unwrap());
+        assert!(!regex.es_valida("2024  mi archivo").unwrap());
+    }
+
+    #[test]
+    fn test152_clases_abreviadas_negadas() {
+        let regex = Regex::new("^\\D\\S\\W$").unwrap();
+        assert!(regex.es_valida("a;;").unwrap());
+        assert!(!regex.es_valida("1;;").unwrap());
+        assert!(!regex.es_valida("a ;").unwrap());
+        assert!(!regex.es_valida("a;_").unwrap());
+    }
+
+    #[test]
+    fn test153_clases_abreviadas_en_corchetes() {
+        let regex = Regex::new("^[\\w.]+$").unwrap();
+        assert!(regex.es_valida("archivo_1.txt").unwrap());
+        assert!(!regex.es_valida("archivo 1.txt").unwrap());
+    }
+
+    #[test]
+    fn test154_clases_abreviadas_en_corchetes_negados() {
+        let regex = Regex::new("^[^\\d\\s]+$").unwrap();
+        assert!(regex.es_valida("hola;").unwrap());
+        assert!(!regex.es_valida("hola 1").unwrap());
+    }
+
+    #[test]
+    fn test155_palabras_repetidas() {
+        let regex = Regex::new("\\b(\\w+) \\1\\b").unwrap();
+        let capturas = regex.captures("dijo que que no").unwrap().unwrap();
+        assert_eq!(capturas.get(1), Some("que"));
+    }
+
+    #[test]
+    fn test156_clase_xdigit() {
+        let regex = Regex::new("^[[:xdigit:]]+$").unwrap();
+        assert!(regex.es_valida("0aF9").unwrap());
+        assert!(!regex.es_valida("0aG9").unwrap());
+    }
+
+    #[test]
+    fn test157_clase_blank() {
+        let regex = Regex::new("a[[:blank:]]b").unwrap();
+        assert!(regex.es_valida("a b").unwrap());
+        assert!(regex.es_valida("a\tb").unwrap());
+        assert!(!regex.es_valida("ab").unwrap());
+    }
+
+    #[test]
+    fn test158_clases_graph_y_print() {
+        let graph = Regex::new("^[[:graph:]]+$").unwrap();
+        let print = Regex::new("^[[:print:]]+$").unwrap();
+        assert!(graph.es_valida("hola;").unwrap());
+        assert!(!graph.es_valida("hola mundo").unwrap());
+        assert!(print.es_valida("hola mundo").unwrap());
+        assert!(!print.es_valida("hola\tmundo").unwrap());
+    }
+
+    #[test]
+    fn test159_clase_cntrl() {
+        let regex = Regex::new("[[:cntrl:]]").unwrap();
+        assert!(regex.es_valida("hola\tmundo").unwrap());
+        assert!(!regex.es_valida("hola mundo").unwrap());
+        let negada = Regex::new("^[^[:cntrl:]]+$").unwrap();
+        assert!(negada.es_valida("hola mundo").unwrap());
+    }
+
+    #[test]
+    fn test160_clase_desconocida() {
+        assert_eq!(
+            Regex::new("[[:digitos:]]").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnClase)
+        );
+        assert_eq!(
+            Regex::new("[[:foo:]]").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnClase)
+        );
+    }
+
+    #[test]
+    fn test161_corchete_con_clase_rango_y_literal() {
+        let regex = Regex::new("^[[:digit:]_a-f]+$").unwrap();
+        assert!(regex.es_valida("3_af0").unwrap());
+        assert!(!regex.es_valida("3_ag0").unwrap());
+        assert!(!regex.es_valida("digit").unwrap());
+    }
+
+    #[test]
+    fn test162_corchete_con_varias_clases() {
+        let regex = Regex::new("^[[:alpha:][:digit:]]+$").unwrap();
+        assert!(regex.es_valida("abc123").unwrap());
+        assert!(!regex.es_valida("abc 123").unwrap());
+    }
+
+    #[test]
+    fn test163_corchete_mixto_negado() {
+        let regex = Regex::new("^[^[:space:][:punct:]x]+$").unwrap();
+        assert!(regex.es_valida("hola123").unwrap());
+        assert!(!regex.es_valida("hola;").unwrap());
+        assert!(!regex.es_valida("hola mundo").unwrap());
+        assert!(!regex.es_valida("xilofon").unwrap());
+    }
+
+    #[test]
+    fn test164_corchete_con_clase_sin_cerrar() {
+        assert_eq!(
+            Regex::new("[[:alpha]").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnCorchetes)
+        );
+    }
+
+    #[test]
+    fn test165_corchete_cerrado_al_comienzo_es_literal() {
+        let regex = Regex::new("^[]a]+$").unwrap();
+        assert!(regex.es_valida("a]a").unwrap());
+        assert!(!regex.es_valida("ab").unwrap());
+        let negado = Regex::new("^[^]a]+$").unwrap();
+        assert!(negado.es_valida("bcd").unwrap());
+        assert!(!negado.es_valida("b]d").unwrap());
+    }
+
+    #[test]
+    fn test166_guion_al_comienzo_o_al_final_es_literal() {
+        let al_final = Regex::new("^[a-]+$").unwrap();
+        assert!(al_final.es_valida("a-a").unwrap());
+        assert!(!al_final.es_valida("b").unwrap());
+        let al_comienzo = Regex::new("^[-a]+$").unwrap();
+        assert!(al_comienzo.es_valida("-a-").unwrap());
+        assert!(!al_comienzo.es_valida("b").unwrap());
+    }
+
+    #[test]
+    fn test167_caret_fuera_del_comienzo_es_literal() {
+        let regex = Regex::new("^[a^]+$").unwrap();
+        assert!(regex.es_valida("a^a").unwrap());
+        assert!(!regex.es_valida("b").unwrap());
+    }
+
+    #[test]
+    fn test168_escapes_en_corchetes() {
+        let regex = Regex::new("^[\\]\\.]+$").unwrap();
+        assert!(regex.es_valida("].].").unwrap());
+        assert!(!regex.es_valida("a").unwrap());
+        let punto = Regex::new("^[\\.]$").unwrap();
+        assert!(punto.es_valida(".").unwrap());
+        assert!(!punto.es_valida("\\").unwrap());
+    }
+
+    #[test]
+    fn test169_rango_invertido() {
+        assert_eq!(
+            Regex::new("[z-a]").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnCorchetes)
+        );
+    }
+
+    #[test]
+    fn test170_llaves_dentro_de_corchetes() {
+        let regex = Regex::new("^[{}|]+$").unwrap();
+        assert!(regex.es_valida("{|}").unwrap());
+        assert!(!regex.es_valida("a").unwrap());
+    }
+
+    #[test]
+    fn test171_llaves_con_numeros_de_varios_digitos() {
+        let regex = Regex::new("^a{10}$").unwrap();
+        assert!(regex.es_valida(&"a".repeat(10)).unwrap());
+        assert!(!regex.es_valida(&"a".repeat(9)).unwrap());
+        let rango = Regex::new("^a{2,15}$").unwrap();
+        assert!(rango.es_valida(&"a".repeat(15)).unwrap());
+        assert!(!rango.es_valida(&"a".repeat(16)).unwrap());
+    }
+
+    #[test]
+    fn test172_llaves_con_espacios() {
+        let regex = Regex::new("^a{ 2 , 3 }$").unwrap();
+        assert!(regex.es_valida("aaa").unwrap());
+        assert!(!regex.es_valida("a").unwrap());
+        let sin_minimo = Regex::new("^a{ ,12}$").unwrap();
+        assert!(sin_minimo.es_valida(&"a".repeat(12)).unwrap());
+        assert!(!sin_minimo.es_valida(&"a".repeat(13)).unwrap());
+    }
+
+    #[test]
+    fn test173_llaves_invalidas() {
+        assert_eq!(
+            Regex::new("a{}").err().map(|error| error.tipo()),
+            Some(Error::LlavesVacias)
+        );
+        assert_eq!(
+            Regex::new("a{,}").err().map(|error| error.tipo()),
+            Some(Error::LlavesVacias)
+        );
+        assert_eq!(
+            Regex::new("a{a}").err().map(|error| error.tipo()),
+            Some(Error::LlavesConContenidoInvalido)
+        );
+        assert_eq!(
+            Regex::new("a{1,2,3}").err().map(|error| error.tipo()),
+            Some(Error::LlavesConContenidoInvalido)
+        );
+        assert_eq!(
+            Regex::new("a{12,3}").err().map(|error| error.tipo()),
+            Some(Error::RangoDeLlavesInvertido)
+        );
+    }
+
+    #[test]
+    fn test174_maximo_de_repeticiones_configurable() {
+        let configuracion = Configuracion {
+            maximo_repeticiones: 10,
+            ..Configuracion::default()
+        };
+        assert_eq!(
+            Regex::con_configuracion("a{11}", &configuracion)
+                .err()
+                .map(|error| error.tipo()),
+            Some(Error::RepeticionesExcedidas)
+        );
+        assert!(Regex::con_configuracion("a{2,10}", &configuracion).is_ok());
+        assert_eq!(
+            Regex::new("a{99999999999999999999999}")
+                .err()
+                .map(|error| error.tipo()),
+            Some(Error::RepeticionesExcedidas)
+        );
+    }
+
+    #[test]
+    fn test175_literales_de_puntuacion() {
+        let regex = Regex::new("^[[:alnum:]._]+@[[:alnum:]-]+\\.com$").unwrap();
+        assert!(regex.es_valida("nombre_1.apellido@fi-uba.com").unwrap());
+        assert!(!regex.es_valida("nombre@fi uba.com").unwrap());
+        let ruta = Regex::new("/usr/local/bin:").unwrap();
+        assert!(ruta.es_valida("PATH=/usr/local/bin:/usr/bin").unwrap());
+        let lista = Regex::new("a, b; c").unwrap();
+        assert!(lista.es_valida("a, b; c").unwrap());
+    }
+
+    #[test]
+    fn test176_literales_no_ascii() {
+        let regex = Regex::new("canci(ó)n").unwrap();
+        let capturas = regex.captures("una canción").unwrap().unwrap();
+        assert_eq!(capturas.get(0), Some("canción"));
+        assert_eq!(capturas.get(1), Some("ó"));
+        assert!(regex.captures("una cancion").unwrap().is_none());
+    }
+
+    #[test]
+    fn test177_linea_con_acentos() {
+        let regex = Regex::new("canción").unwrap();
+        assert!(regex.es_valida("Una canción en español").unwrap());
+        assert!(!regex.es_valida("Una cancion en espanol").unwrap());
+    }
+
+    #[test]
+    fn test178_comodin_consume_un_caracter() {
+        let regex = Regex::new("^a.b$").unwrap();
+        assert!(regex.es_valida("añb").unwrap());
+        assert!(regex.es_valida("a😀b").unwrap());
+        assert!(!regex.es_valida("añña").unwrap());
+        let dos = Regex::new("^.{2}$").unwrap();
+        assert!(dos.es_valida("¿é").unwrap());
+        assert!(!dos.es_valida("é").unwrap());
+    }
+
+    #[test]
+    fn test179_clases_con_semantica_unicode() {
+        let alpha = Regex::new("^[[:alpha:]]+$").unwrap();
+        assert!(alpha.es_valida("Ñandú").unwrap());
+        let punct = Regex::new("^[[:punct:]]+$").unwrap();
+        assert!(punct.es_valida("¿¡;").unwrap());
+        assert!(!punct.es_valida("¿a?").unwrap());
+        let palabra = Regex::new("^\\w+$").unwrap();
+        assert!(palabra.es_valida("pingüino").unwrap());
+        let digito = Regex::new("\\d").unwrap();
+        assert!(!digito.es_valida("٣").unwrap());
+    }
+
+    #[test]
+    fn test180_propiedad_categoria_general() {
+        let letras = Regex::new("^\\p{L}+$").unwrap();
+        assert!(letras.es_valida("Ñandúλ漢").unwrap());
+        assert!(!letras.es_valida("ab1").unwrap());
+        let mayusculas = Regex::new("^\\p{Lu}\\p{Ll}+$").unwrap();
+        assert!(mayusculas.es_valida("Ásia").unwrap());
+        assert!(!mayusculas.es_valida("ásia").unwrap());
+        let numeros = Regex::new("^\\pN+$").unwrap();
+        assert!(numeros.es_valida("12٣½").unwrap());
+    }
+
+    #[test]
+    fn test181_propiedad_escritura() {
+        let griego = Regex::new("^\\p{Greek}+$").unwrap();
+        assert!(griego.es_valida("αβγΩ").unwrap());
+        assert!(!griego.es_valida("αβc").unwrap());
+        let han = Regex::new("\\p{Han}").unwrap();
+        assert!(han.es_valida("hola 漢字").unwrap());
+        assert!(!han.es_valida("hola ひらがな").unwrap());
+    }
+
+    #[test]
+    fn test182_propiedad_negada() {
+        let regex = Regex::new("^\\P{L}+$").unwrap();
+        assert!(regex.es_valida("123 ;").unwrap());
+        assert!(!regex.es_valida("12é").unwrap());
+        let corchete = Regex::new("^[\\p{Greek}\\d]+$").unwrap();
+        assert!(corchete.es_valida("π314").unwrap());
+        assert!(!corchete.es_valida("pi314").unwrap());
+    }
+
+    #[test]
+    fn test183_propiedad_desconocida() {
+        assert_eq!(
+            Regex::new("\\p{Klingon}").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnClase)
+        );
+        assert_eq!(
+            Regex::new("\\pX").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnClase)
+        );
+    }
+
+    #[test]
+    fn test184_ignorar_mayusculas_en_configuracion() {
+        let configuracion = Configuracion {
+            ignorar_mayusculas: true,
+            ..Configuracion::default()
+        };
+        let regex = Regex::con_configuracion("error", &configuracion).unwrap();
+        assert!(regex.es_valida("Error: falta").unwrap());
+        assert!(regex.es_valida("ERROR: falta").unwrap());
+        assert!(!regex.es_valida("erro: falta").unwrap());
+        assert!(!Regex::new("error").unwrap().es_valida("ERROR").unwrap());
+    }
+
+    #[test]
+    fn test185_ignorar_mayusculas_en_clases() {
+        let configuracion = Configuracion {
+            ignorar_mayusculas: true,
+            ..Configuracion::default()
+        };
+        let rango = Regex::con_configuracion("^[a-c]+$", &configuracion).unwrap();
+        assert!(rango.es_valida("aBc").unwrap());
+        let negado = Regex::con_configuracion("^[^a]$", &configuracion).unwrap();
+        assert!(!negado.es_valida("A").unwrap());
+        let mayusculas = Regex::con_configuracion("^[[:upper:]]+$", &configuracion).unwrap();
+        assert!(mayusculas.es_valida("hola").unwrap());
+    }
+
+    #[test]
+    fn test186_ignorar_mayusculas_unicode() {
+        let configuracion = Configuracion {
+            ignorar_mayusculas: true,
+            ..Configuracion::default()
+        };
+        let regex = Regex::con_configuracion("^canción ñandú$", &configuracion).unwrap();
+        assert!(regex.es_valida("CANCIÓN ÑANDÚ").unwrap());
+        let sigma = Regex::con_configuracion("^σ$", &configuracion).unwrap();
+        assert!(sigma.es_valida("Σ").unwrap());
+        assert!(sigma.es_valida("ς").unwrap());
+    }
+
+    #[test]
+    fn test187_modificadores_en_linea() {
+        let regex = Regex::new("a(?i)b(?-i)c").unwrap();
+        assert!(regex.es_valida("aBc").unwrap());
+        assert!(!regex.es_valida("ABc").unwrap());
+        assert!(!regex.es_valida("aBC").unwrap());
+        let grupo = Regex::new("(?i:ho)la").unwrap();
+        assert!(grupo.es_valida("HOla").unwrap());
+        assert!(!grupo.es_valida("HOLA").unwrap());
+        let interno = Regex::new("(x(?i)y)z").unwrap();
+        assert!(interno.es_valida("xYz").unwrap());
+        assert!(!interno.es_valida("xYZ").unwrap());
+    }
+
+    #[test]
+    fn test188_referencia_sin_mayusculas() {
+        let regex = Regex::new("(?i)(\\w+) \\1").unwrap();
+        assert!(regex.es_valida("Hola HOLA").unwrap());
+        assert!(!regex.es_valida("Hola Chau").unwrap());
+    }
+
+    #[test]
+    fn test189_escapes_de_control() {
+        let regex = Regex::new("^nombre\\tedad$").unwrap();
+        assert!(regex.es_valida("nombre\tedad").unwrap());
+        assert!(!regex.es_valida("nombretedad").unwrap());
+        let otros = Regex::new("\\r\\n\\f\\v\\0").unwrap();
+        assert!(otros.es_valida("a\r\n\u{0C}\u{0B}\0b").unwrap());
+    }
+
+    #[test]
+    fn test190_escapes_hexadecimales() {
+        let regex = Regex::new("^\\x41\\x{42}\\u{00F1}$").unwrap();
+        assert!(regex.es_valida("ABñ").unwrap());
+        assert!(!regex.es_valida("AB").unwrap());
+        let emoji = Regex::new("\\u{1F600}").unwrap();
+        assert!(emoji.es_valida("hola 😀").unwrap());
+    }
+
+    #[test]
+    fn test191_escapes_en_corchetes() {
+        let regex = Regex::new("^[^\\t,]+(\\t[^\\t,]+)*$").unwrap();
+        assert!(regex.es_valida("a\tb\tc").unwrap());
+        assert!(!regex.es_valida("a,b").unwrap());
+        let rango = Regex::new("[\\x00-\\x1F]").unwrap();
+        assert!(rango.es_valida("hola\u{1B}").unwrap());
+        assert!(!rango.es_valida("hola").unwrap());
+    }
+
+    #[test]
+    fn test192_escapes_invalidos() {
+        assert_eq!(
+            Regex::new("\\x4").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnEscape)
+        );
+        assert_eq!(
+            Regex::new("\\xZZ").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnEscape)
+        );
+        assert_eq!(
+            Regex::new("\\u{110000}").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnEscape)
+        );
+        assert_eq!(
+            Regex::new("\\u41").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnEscape)
+        );
+    }
+
+    #[test]
+    fn test193_dialecto_basico_operadores_con_barra() {
+        let regex = Regex::con_dialecto("a\\{2\\}b", Dialecto::Basico).unwrap();
+        assert!(regex.es_valida("xaab").unwrap());
+        assert!(!regex.es_valida("xab").unwrap());
+        let regex = Regex::con_dialecto("\\(ab\\)\\1", Dialecto::Basico).unwrap();
+        assert!(regex.es_valida("abab").unwrap());
+        assert!(!regex.es_valida("abba").unwrap());
+        let regex = Regex::con_dialecto("^gato\\|perro$", Dialecto::Basico).unwrap();
+        assert!(regex.es_valida("perro").unwrap());
+        assert!(regex.es_valida("gato").unwrap());
+        assert!(!regex.es_valida("loro").unwrap());
+        let regex = Regex::con_dialecto("ab\\+c\\?", Dialecto::Basico).unwrap();
+        assert!(regex.es_valida("abbb").unwrap());
+        assert!(!regex.es_valida("ac").unwrap());
+    }
+
+    #[test]
+    fn test194_dialecto_basico_literales_sin_barra() {
+        let regex = Regex::con_dialecto("a+b?{2}(c|d)", Dialecto::Basico).unwrap();
+        assert!(regex.es_valida("a+b?{2}(c|d)").unwrap());
+        assert!(!regex.es_valida("aab").unwrap());
+        let regex = Regex::con_dialecto("*a", Dialecto::Basico).unwrap();
+        assert!(regex.es_valida("x*a").unwrap());
+        assert!(!regex.es_valida("a").unwrap());
+        let regex = Regex::con_dialecto("a^b$c", Dialecto::Basico).unwrap();
+        assert!(regex.es_valida("a^b$c").unwrap());
+        let regex = Regex::con_dialecto("[a\\]", Dialecto::Basico).unwrap();
+        assert!(regex.es_valida("\\").unwrap());
+    }
+
+    #[test]
+    fn test195_dialecto_extendido() {
+        let regex = Regex::con_dialecto("a{2}(b|c)+", Dialecto::Extendido).unwrap();
+        assert!(regex.es_valida("aabcb").unwrap());
+        assert!(!regex.es_valida("abc").unwrap());
+        let regex = Regex::con_dialecto("\\d", Dialecto::Extendido).unwrap();
+        assert!(regex.es_valida("d").unwrap());
+        assert!(!regex.es_valida("5").unwrap());
+        let regex = Regex::con_dialecto("[\\]]", Dialecto::Extendido).unwrap();
+        assert!(regex.es_valida("]").unwrap());
+        assert_eq!(
+            Regex::con_dialecto("(?:a)", Dialecto::Extendido)
+                .err()
+                .map(|error| error.tipo()),
+            Some(Error::ErrorEnRepeticion)
+        );
+    }
+
+    #[test]
+    fn test196_dialecto_extendido_repeticiones_seguidas() {
+        let regex = Regex::con_dialecto("^a*?$", Dialecto::Extendido).unwrap();
+        assert!(regex.es_valida("aaa").unwrap());
+        assert!(regex.es_valida("").unwrap());
+        let regex = Regex::con_dialecto("^a{2}{2}$", Dialecto::Extendido).unwrap();
+        assert!(regex.es_valida("aaaa").unwrap());
+        assert!(!regex.es_valida("aa").unwrap());
+    }
+
+    #[test]
+    fn test197_dialecto_perl() {
+        let regex = Regex::con_dialecto("(?<!a)\\d+?x", Dialecto::Perl).unwrap();
+        assert!(regex.es_valida("b12x").unwrap());
+        assert!(!regex.es_valida("a1x").unwrap());
+        assert_eq!(
+            Regex::con_dialecto("\\p{L}\\t", Dialecto::Perl)
+                .unwrap()
+                .es_valida("ñ\t"),
+            Ok(true)
+        );
+        assert!(Regex::new("a\\{2\\}").unwrap().es_valida("a{2}").unwrap());
+    }
+
+    #[test]
+    fn test198_punto_y_saltos_de_linea() {
+        let regex = Regex::new("a.b").unwrap();
+        assert!(!regex.es_valida("a\nb").unwrap());
+        assert!(regex.es_valida("a-b").unwrap());
+        let regex = Regex::new("a\\nb").unwrap();
+        assert!(regex.es_valida("xa\nb").unwrap());
+        let regex = Regex::new("(?s)a.b").unwrap();
+        assert!(regex.es_valida("a\nb").unwrap());
+        let regex = Regex::new("(?s:a.)b.c").unwrap();
+        assert!(regex.es_valida("a\nb-c").unwrap());
+        assert!(!regex.es_valida("a\nb\nc").unwrap());
+    }
+
+    #[test]
+    fn test199_anclas_por_linea() {
+        let regex = Regex::new("^dos$").unwrap();
+        assert!(!regex.es_valida("uno\ndos\ntres").unwrap());
+        let regex = Regex::new("(?m)^dos$").unwrap();
+        assert!(regex.es_valida("uno\ndos\ntres").unwrap());
+        assert!(!regex.es_valida("uno\ndoss").unwrap());
+        let regex = Regex::new("(?m)(?-m)^dos").unwrap();
+        assert!(!regex.es_valida("uno\ndos").unwrap());
+    }
+
+    #[test]
+    fn test200_varios_modificadores() {
+        let regex = Regex::new("(?is)A.B").unwrap();
+        assert!(regex.es_valida("a\nb").unwrap());
+        let regex = Regex::new("(?ims-i)^A.b").unwrap();
+        assert!(regex.es_valida("x\nA\nb").unwrap());
+        assert!(!regex.es_valida("x\na\nb").unwrap());
+        assert_eq!(
+            Regex::new("(?-)a").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnParentesis)
+        );
+        assert_eq!(
+            Regex::new("(?x)a").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnParentesis)
+        );
+    }
+
+    #[test]
+    fn test201_coincidencias() {
+        let regex = Regex::new("a+").unwrap();
+        assert_eq!(
+            regex.coincidencias("baacaaa").unwrap(),
+            vec![(1, 3), (4, 7)]
+        );
+        let regex = Regex::new("(?s)fin.inicio").unwrap();
+        assert_eq!(
+            regex.coincidencias("uno fin\ninicio dos").unwrap(),
+            vec![(4, 14)]
+        );
+        let regex = Regex::new("x*").unwrap();
+        assert_eq!(
+            regex.coincidencias("ñx").unwrap(),
+            vec![(0, 0), (2, 3), (3, 3)]
+        );
+        assert_eq!(
+            Regex::new("z").unwrap().coincidencias("abc").unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test202_error_con_posicion_en_llaves() {
+        let error = Regex::new("a{3,x}").err().unwrap();
+        assert_eq!(error.tipo(), Error::LlavesConContenidoInvalido);
+        assert_eq!(error.span(), (4, 5));
+        assert_eq!(
+            error.to_string(),
+            format!("{}\na{{3,x}}\n    ^", Error::LlavesConContenidoInvalido)
+        );
+        assert_eq!(Regex::new("ab{2").err().unwrap().span(), (2, 3));
+        assert_eq!(Regex::new("a{5,2}").err().unwrap().span(), (2, 5));
+        assert_eq!(Regex::new("xa{}").err().unwrap().span(), (2, 4));
+    }
+
+    #[test]
+    fn test203_error_con_posicion_en_grupos_y_corchetes() {
+        assert_eq!(Regex::new("ab(cd").err().unwrap().span(), (2, 3));
+        assert_eq!(Regex::new("ab)cd").err().unwrap().span(), (2, 3));
+        assert_eq!(Regex::new("x[z-a]").err().unwrap().span(), (2, 5));
+        assert_eq!(Regex::new("x[abc").err().unwrap().span(), (1, 2));
+        assert_eq!(Regex::new("(a)\\2").err().unwrap().span(), (3, 5));
+        assert_eq!(Regex::new("a|*b").err().unwrap().span(), (2, 3));
+        assert_eq!(Regex::new("[[:foo:]]").err().unwrap().span(), (1, 8));
+    }
+
+    #[test]
+    fn test204_error_con_posicion_y_caracteres_utf8() {
+        let error = Regex::new("ñandú\\xZZ").err().unwrap();
+        assert_eq!(error.tipo(), Error::ErrorEnEscape);
+        assert_eq!(error.span(), (7, 11));
+        assert_eq!(
+            error.to_string(),
+            format!("{}\nñandú\\xZZ\n     ^^^^", Error::ErrorEnEscape)
+        );
+        let error = Regex::new("ab\\").err().unwrap();
+        assert_eq!(error.span(), (2, 3));
+    }
+
+    #[test]
+    fn test205_arbol_de_sintaxis() {
+        let configuracion = Configuracion::default();
+        let mut analizador = Analizador::new("a(bc)*|d", &configuracion);
+        let alternativas = analizador.analizar().unwrap();
+        assert_eq!(alternativas.len(), 2);
+        assert_eq!(alternativas[0].len(), 2);
+        let grupo = &alternativas[0][1];
+        assert_eq!((grupo.inicio, grupo.fin), (1, 6));
+        match &grupo.tipo {
+            TipoDeNodo::Repeticion { nodo, repeticiones } => {
+                assert_eq!(*repeticiones, Repeticion::Alguna);
+                assert_eq!((nodo.inicio, nodo.fin), (1, 5));
+            }
+            _ => panic!("se esperaba una repetición"),
+        }
+        assert_eq!((alternativas[1][0].inicio, alternativas[1][0].fin), (7, 8));
+        assert_eq!(analizador.nombres_capturas(), vec![None, None]);
+    }
+
+    #[test]
+    fn test206_repeticiones_anidadas_en_tiempo_lineal() {
+        let regex = Regex::new("(a*)*b").unwrap();
+        let linea = "a".repeat(5000);
+        assert!(!regex.es_valida(&linea).unwrap());
+        assert!(regex.es_valida(&(linea.clone() + "b")).unwrap());
+        let regex = Regex::new("(a|aa)+$").unwrap();
+        assert!(!regex.es_valida(&(linea + "!")).unwrap());
+    }
+
+    #[test]
+    fn test207_muchos_comodines_en_una_linea_larga() {
+        let regex = Regex::new(".*a.*b.*c.*d.*e.*f.*g.*z").unwrap();
+        let linea = "abcdefg".repeat(3000);
+        assert!(!regex.es_valida(&linea).unwrap());
+        assert!(regex.es_valida(&(linea + "z")).unwrap());
+    }
+
+    #[test]
+    fn test208_se_evalua_con_backtracking_solo_si_hace_falta() {
+        assert!(
+            !Regex::new("(a|b)*?c{2,3}[x-z]\\b$")
+                .unwrap()
+                .programa
+                .necesita_backtracking
+        );
+        assert!(Regex::new("(a)\\1").unwrap().programa.necesita_backtracking);
+        assert!(Regex::new("a(?=b)").unwrap().programa.necesita_backtracking);
+        assert!(
+            Regex::new("(?<!a)b")
+                .unwrap()
+                .programa
+                .necesita_backtracking
+        );
+        assert!(
+            Regex::new("(?>a*)b")
+                .unwrap()
+                .programa
+                .necesita_backtracking
+        );
+        assert!(Regex::new("a*+b").unwrap().programa.necesita_backtracking);
+        assert!(Regex::new("(a{1000}){900}").is_ok());
+        let error = Regex::new("(a{1000}){2000}").err().unwrap();
+        assert_eq!(error.tipo(), Error::ExpresionDemasiadoGrande);
+    }
+
+    #[test]
+    fn test209_capturas_iguales_a_las_del_backtracking() {
+        let casos = [
+            ("(a*)*b", "aab"),
+            ("(b*(|a))*", "abab"),
+            ("(.*?)*", "ab"),
+            ("((a?)?.*?)*", "baab"),
+            ("(a|ab)(c|bcd)(d*)", "abcd"),
+            ("(?i)(?<x>A+?)(b)?", "aaB"),
+            ("(a{0,3}?)(a*)", "aaaa"),
+            ("(?m)^(\\w+)$", "uno\ndos"),
+        ];
+        for (expresion, texto) in casos {
+            let regex = Regex::new(expresion).unwrap();
+            let backtracking = Regex::new(&format!("(?=){}", expresion)).unwrap();
+            assert!(!regex.programa.necesita_backtracking);
+            assert!(backtracking.programa.necesita_backtracking);
+            assert_eq!(
+                regex.captures(texto).unwrap(),
+                backtracking.captures(texto).unwrap()
+            );
+            assert_eq!(
+                regex.coincidencias(texto).unwrap(),
+                backtracking.coincidencias(texto).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test210_vueltas_vacias_con_la_maquina_de_pike() {
+        let regex = Regex::new("(b*(|a))*").unwrap();
+        let capturas = regex.captures("abab").unwrap().unwrap();
+        assert_eq!(capturas.get(0), Some(""));
+        assert_eq!(capturas.span(1), Some((0, 0)));
+        assert_eq!(capturas.span(2), Some((0, 0)));
+        let regex = Regex::new("(.*?)*").unwrap();
+        assert_eq!(
+            regex.coincidencias("ab").unwrap(),
+            vec![(0, 0), (1, 1), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn test211_escrituras_de_scripts_txt() {
+        let latin = Regex::new("^\\p{Latin}+$").unwrap();
+        assert!(latin.es_valida("ªºʰⅠK").unwrap());
+        let griego = Regex::new("^\\p{Greek}$").unwrap();
+        assert!(griego.es_valida("Ω").unwrap());
+        let comunes = Regex::new("[\\p{Thai}\\p{Arabic}\\p{Devanagari}\\p{Katakana}]").unwrap();
+        assert!(!comunes.es_valida("฿،।ー").unwrap());
+        assert!(comunes.es_valida("กعकカ").unwrap());
+    }
+
+    #[test]
+    fn test212_dialecto_basico_asterisco_luego_del_caret() {
+        let regex = Regex::con_dialecto("^*a", Dialecto::Basico).unwrap();
+        assert!(regex.es_valida("*a").unwrap());
+        assert!(!regex.es_valida("aa").unwrap());
+        assert!(!regex.es_valida("a+").unwrap());
+        let regex = Regex::con_dialecto("x\\|^*b", Dialecto::Basico).unwrap();
+        assert!(regex.es_valida("*b").unwrap());
+        assert!(!regex.es_valida("b").unwrap());
+        let regex = Regex::con_dialecto("^a*", Dialecto::Basico).unwrap();
+        assert!(regex.es_valida("b").unwrap());
+    }
+
+    #[test]
+    fn test213_anclas_con_repeticion() {
+        for expresion in ["a\\b?b", "a$?b", "a\\b{0}b", "a\\b*b", "(a)\\b?b\\1?"] {
+            let regex = Regex::new(expresion).unwrap();
+            assert!(regex.es_valida("ab").unwrap());
+        }
+        let regex = Regex::new("\\b{0}x").unwrap();
+        assert!(regex.es_valida("ax").unwrap());
+        let regex = Regex::new("a\\b+").unwrap();
+        assert!(!regex.es_valida("ab").unwrap());
+        assert!(regex.es_valida("a b").unwrap());
+        let regex = Regex::new("(a)\\b{1,2}\\1?").unwrap();
+        assert!(!regex.es_valida("ab").unwrap());
+    }
+
+    #[test]
+    fn test214_rangos_grandes_en_corchetes() {
+        for _ in 0..20 {
+            let regex = Regex::new("^[\\x{80}-\\x{10FFFF}]+$").unwrap();
+            assert!(regex.es_valida("ñú😀").unwrap());
+            assert!(!regex.es_valida("ñu").unwrap());
+        }
+        let regex = Regex::new("^[^\\x{80}-\\x{10FFFF}x]+$").unwrap();
+        assert!(regex.es_valida("abc").unwrap());
+        assert!(!regex.es_valida("abx").unwrap());
+        let regex = Regex::new("(?i)^[a-cñ[:digit:]]+$").unwrap();
+        assert!(regex.es_valida("aBcÑ1").unwrap());
+        assert!(!regex.es_valida("abd").unwrap());
+    }
+
+    #[test]
+    fn test215_backtracking_en_una_linea_larga() {
+        let linea = "ab".repeat(100_000) + "c";
+        for expresion in ["(?:ab)*+c", "(?=a)(?:ab)*c", "(\\w)(?:ab)*b?\\1?c"] {
+            let regex = Regex::new(expresion).unwrap();
+            assert!(regex.programa.necesita_backtracking);
+            assert!(regex.es_valida(&linea).unwrap());
+        }
+        let regex = Regex::new("(a)*\\1b").unwrap();
+        assert!(regex.es_valida(&("a".repeat(50_000) + "b")).unwrap());
+        let regex = Regex::new("(?=)(ab)*c").unwrap();
+        let capturas = regex.captures(&linea).unwrap().unwrap();
+        assert_eq!(capturas.span(0), Some((0, linea.len())));
+        assert_eq!(capturas.span(1), Some((linea.len() - 3, linea.len() - 1)));
+    }
+
+    #[test]
+    fn test216_una_vuelta_vacia_sale_de_la_repeticion() {
+        let casos = [
+            ("([ab]*?)?a{0,2}", "baa ", (0, 0)),
+            ("(?:.??)+|b", "ab", (0, 0)),
+            ("(|a)*", "a", (0, 0)),
+            ("(.*?)*", "Aab", (0, 0)),
+            ("((a?)*?)*", "a", (0, 0)),
+            ("(?:(|a)b)*?$|a", "abab", (0, 4)),
+        ];
+        for (expresion, texto, esperado) in casos {
+            for expresion in [expresion.to_string(), format!("(?=){expresion}")] {
+                let regex = Regex::new(&expresion).unwrap();
+                let capturas = regex.captures(texto).unwrap().unwrap();
+                assert_eq!(capturas.span(0), Some(esperado));
+            }
+        }
+        for expresion in ["(?:(|a)+)*?b|a", "(?=)(?:(|a)+)*?b|a"] {
+            let capturas = Regex::new(expresion)
+                .unwrap()
+                .captures("ab")
+                .unwrap()
+                .unwrap();
+            assert_eq!(capturas.span(0), Some((0, 2)));
+            assert_eq!(capturas.span(1), Some((1, 1)));
+        }
+    }
+
+    #[test]
+    fn test217_backtracking_exponencial() {
+        let regex = Regex::new("(?=)(?:(((.{0,2}|[ab]*){2})+?)+x)").unwrap();
+        for linea in ["b", "bb", "bbc", "bbca", "bbcab"] {
+            assert!(!regex.es_valida(linea).unwrap());
+        }
+        assert!(regex.es_valida(&("ab".repeat(2000) + "x")).unwrap());
+        let regex = Regex::new("(?=)(a|aa)+$").unwrap();
+        assert!(!regex.es_valida(&("a".repeat(5000) + "b")).unwrap());
+        let regex = Regex::new("(a+)+\\1b").unwrap();
+        let linea = "a".repeat(40) + "c";
+        assert_eq!(regex.es_valida(&linea), Err(Error::BacktrackingExcedido));
+        assert_eq!(
+            regex.coincidencias(&linea),
+            Err(Error::BacktrackingExcedido)
+        );
+        assert!(regex.captures(&linea).is_err());
+        assert!(regex.es_valida("aaab").unwrap());
+    }
+
+    #[test]
+    fn test218_repeticiones_seguidas_en_el_dialecto_de_perl() {
+        for expresion in ["a**", "a+*", "^a{2}{3}$", "a*??", "a{2}+?"] {
+            assert_eq!(
+                Regex::new(expresion).err().map(|error| error.tipo()),
+                Some(Error::ErrorEnRepeticion)
+            );
+        }
+        assert_eq!(Regex::new("a+*").err().unwrap().span(), (2, 3));
+        assert_eq!(Regex::new("^a{2}{3}$").err().unwrap().span(), (5, 6));
+        let regex = Regex::new("^(?:a{2}){3}$").unwrap();
+        assert!(regex.es_valida("aaaaaa").unwrap());
+        assert!(!regex.es_valida("aaaa").unwrap());
+        let regex = Regex::con_dialecto("^a{2}{3}$", Dialecto::Extendido).unwrap();
+        assert!(regex.es_valida("aaaaaa").unwrap());
+        assert!(!regex.es_valida("aaaa").unwrap());
+    }
+
+    #[test]
+    fn test219_escape_hexadecimal_sin_cerrar() {
+        let error = Regex::new("\\x{41").err().unwrap();
+        assert_eq!(error.tipo(), Error::ErrorEnEscape);
+        assert_eq!(error.span(), (0, 5));
+        let error = Regex::new("a\\u{263A").err().unwrap();
+        assert_eq!(error.tipo(), Error::ErrorEnEscape);
+        assert_eq!(error.span(), (1, 8));
+        let regex = Regex::new("^\\x{41}{2}$").unwrap();
+        assert!(regex.es_valida("AA").unwrap());
+        let error = Regex::new("\\x{41}{2").err().unwrap();
+        assert_eq!(error.tipo(), Error::ErrorEnLlaves);
+        assert_eq!(error.span(), (6, 7));
+    }
+
+    #[test]
+    fn test220_todas_las_escrituras_de_scripts_txt() {
+        let regex = Regex::new("^\\p{Cherokee}\\p{Old_Italic}\\p{Common}\\p{Inherited}$").unwrap();
+        assert!(regex.es_valida("Ꭰ𐌀!\u{0301}").unwrap());
+        assert!(!regex.es_valida("a𐌀!\u{0301}").unwrap());
+        let regex = Regex::new("^\\P{Tamil}$").unwrap();
+        assert!(regex.es_valida("a").unwrap());
+        assert!(!regex.es_valida("க").unwrap());
+        let error = Regex::new("\\p{Klingon}").err().unwrap();
+        assert_eq!(error.tipo(), Error::ErrorEnClase);
+        assert_eq!(error.span(), (0, 11));
         assert_eq!(
-            Regex::es_valida_general("ab.?d", "hola abcd chau").unwrap(),
-            true
+            Regex::new("\\p{Unknown}").err().map(|error| error.tipo()),
+            Some(Error::ErrorEnClase)
         );
+        let error = Regex::new("a\\p{L").err().unwrap();
+        assert_eq!(error.tipo(), Error::ErrorEnClase);
+        assert_eq!(error.span(), (1, 5));
     }
 }
@@ -3,11 +3,11 @@
 /// - Exacta: se espera que se repita exactamente la cantidad de veces indicada.
 /// - Alguna: se espera que se repita alguna vez.
 /// - Rango: se espera que se repita una cantidad de veces dentro de un rango, donde
-///se guarda el mínimo y máximo de repeticiones.
-/// Además, las repeticiones Exacta y Alguna cuentan con un booleano que indica
-/// si la repetición es negada o no. Esto nos sirve para saber si se espera que
-/// se repita exactamente la cantidad de veces indicada o no se espera que se repita,
-/// como por ejemplo en los casos del operador de negación ^ dentro de los corchetes.
+///   se guarda el mínimo y máximo de repeticiones.
+/// - Perezosa: como el rango, pero se intenta repetir la menor cantidad de veces posible
+///   y solo se agregan repeticiones si lo que sigue en la expresión no se cumple.
+/// - Posesiva: como el rango, pero una vez que se repitió todo lo posible no se devuelven
+///   caracteres aunque lo que sigue en la expresión no se cumpla.
 pub enum Repeticion {
     Exacta(usize),
     Alguna,
@@ -15,4 +15,54 @@ pub enum Repeticion {
         min: Option<usize>,
         max: Option<usize>,
     },
+    Perezosa {
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+    Posesiva {
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+}
+
+impl Repeticion {
+    ///Devuelve la cantidad mínima y máxima de repeticiones permitidas.
+    ///Un máximo en None indica que no hay límite superior.
+    pub fn limites(&self) -> (usize, Option<usize>) {
+        match self {
+            Repeticion::Exacta(n) => (*n, Some(*n)),
+            Repeticion::Alguna => (0, None),
+            Repeticion::Rango { min, max }
+            | Repeticion::Perezosa { min, max }
+            | Repeticion::Posesiva { min, max } => (min.unwrap_or(0), *max),
+        }
+    }
+
+    ///Devuelve la versión perezosa de la repetición, con los mismos límites.
+    pub fn perezosa(&self) -> Repeticion {
+        let (min, max) = self.limites();
+        Repeticion::Perezosa {
+            min: Some(min),
+            max,
+        }
+    }
+
+    ///Indica si la repetición es perezosa.
+    pub fn es_perezosa(&self) -> bool {
+        matches!(self, Repeticion::Perezosa { .. })
+    }
+
+    ///Devuelve la versión posesiva de la repetición, con los mismos límites.
+    pub fn posesiva(&self) -> Repeticion {
+        let (min, max) = self.limites();
+        Repeticion::Posesiva {
+            min: Some(min),
+            max,
+        }
+    }
+
+    ///Indica si la repetición es posesiva.
+    pub fn es_posesiva(&self) -> bool {
+        matches!(self, Repeticion::Posesiva { .. })
+    }
 }
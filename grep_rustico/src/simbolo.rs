@@ -0,0 +1,9 @@
+///Representa un caracter de la expresión regular ya interpretado según su dialecto.
+/// - Operador: un caracter con significado especial, como una repetición, un paréntesis o
+///   el comienzo de un corchete, sin importar si en el dialecto se escribe con barra o sin ella.
+/// - Literal: un caracter que se espera encontrar tal cual en la línea.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Simbolo {
+    Operador(char),
+    Literal(char),
+}
@@ -0,0 +1,112 @@
+use crate::ast::{NodoAst, TipoDeNodo};
+use crate::caracter::Caracter;
+use crate::configuracion::Configuracion;
+use crate::grupo::{Grupo, TipoDeGrupo};
+use crate::paso_regex::{Elemento, PasoRegex};
+use crate::repeticion::Repeticion;
+
+///Construye un paso que contiene un grupo sin captura con las alternativas indicadas.
+fn fabricar_paso_grupo(alternativas: Vec<Vec<PasoRegex>>) -> PasoRegex {
+    PasoRegex {
+        repeticiones: Repeticion::Exacta(1),
+        ignorar_mayusculas: false,
+        elemento: Elemento::Grupo(Grupo {
+            alternativas,
+            captura: None,
+            tipo: TipoDeGrupo::Comun,
+        }),
+    }
+}
+
+///Traduce el árbol de sintaxis de una expresión regular a los pasos que se evalúan.
+///Si la expresión tiene más de una alternativa, se devuelve un único paso de tipo grupo
+///que las contiene.
+pub(crate) fn traducir(
+    alternativas: &[Vec<NodoAst>],
+    configuracion: &Configuracion,
+) -> Vec<PasoRegex> {
+    let mut alternativas = traducir_alternativas(alternativas, configuracion);
+    if alternativas.len() == 1 {
+        alternativas.remove(0)
+    } else {
+        vec![fabricar_paso_grupo(alternativas)]
+    }
+}
+
+///Traduce cada una de las alternativas de una expresión o de un grupo.
+///Los modificadores como (?i) cambian la configuración hasta el final del grupo,
+///incluidas las alternativas que siguen.
+fn traducir_alternativas(
+    alternativas: &[Vec<NodoAst>],
+    configuracion: &Configuracion,
+) -> Vec<Vec<PasoRegex>> {
+    let mut configuracion = configuracion.clone();
+    let mut traducidas: Vec<Vec<PasoRegex>> = Vec::new();
+    for nodos in alternativas {
+        let mut pasos: Vec<PasoRegex> = Vec::new();
+        for nodo in nodos {
+            if let Some(paso) = traducir_nodo(nodo, &mut configuracion) {
+                pasos.push(paso);
+            }
+        }
+        traducidas.push(pasos);
+    }
+    traducidas
+}
+
+///Traduce un nodo al paso que le corresponde según la configuración.
+/// - El punto es un comodín que coincide con los saltos de línea solo con (?s).
+/// - El caret y el dolar son el inicio y el final del texto o, con (?m), de cada línea.
+/// - Si una repetición se aplica sobre otra, como en a*? en el dialecto extendido,
+///   la anterior queda dentro de un grupo sin captura, como en (?:a*)?.
+/// - Un modificador no tiene paso: cambia la configuración para los nodos que le siguen.
+///
+///Cada paso se compara sin distinguir mayúsculas si así lo indica la configuración.
+fn traducir_nodo(nodo: &NodoAst, configuracion: &mut Configuracion) -> Option<PasoRegex> {
+    let elemento = match &nodo.tipo {
+        TipoDeNodo::Caracter(caracter) => Elemento::Caracter(caracter.clone()),
+        TipoDeNodo::Punto if configuracion.punto_incluye_saltos => {
+            Elemento::Caracter(Caracter::ComodinConSaltos)
+        }
+        TipoDeNodo::Punto => Elemento::Caracter(Caracter::Comodin),
+        TipoDeNodo::Caret if configuracion.anclas_por_linea => {
+            Elemento::Caracter(Caracter::InicioDeLinea)
+        }
+        TipoDeNodo::Caret => Elemento::Caracter(Caracter::Caret),
+        TipoDeNodo::Dolar if configuracion.anclas_por_linea => {
+            Elemento::Caracter(Caracter::FinDeLinea)
+        }
+        TipoDeNodo::Dolar => Elemento::Caracter(Caracter::Dolar),
+        TipoDeNodo::Repeticion { nodo, repeticiones } => {
+            let mut paso = traducir_nodo(nodo, configuracion)?;
+            if paso.repeticiones != Repeticion::Exacta(1) {
+                paso = fabricar_paso_grupo(vec![vec![paso]]);
+            }
+            paso.repeticiones = repeticiones.clone();
+            return Some(paso);
+        }
+        TipoDeNodo::Grupo {
+            alternativas,
+            captura,
+            tipo,
+            modificadores,
+        } => {
+            let mut interna = configuracion.clone();
+            modificadores.aplicar(&mut interna);
+            Elemento::Grupo(Grupo {
+                alternativas: traducir_alternativas(alternativas, &interna),
+                captura: *captura,
+                tipo: tipo.clone(),
+            })
+        }
+        TipoDeNodo::Modificadores(modificadores) => {
+            modificadores.aplicar(configuracion);
+            return None;
+        }
+    };
+    Some(PasoRegex {
+        elemento,
+        repeticiones: Repeticion::Exacta(1),
+        ignorar_mayusculas: configuracion.ignorar_mayusculas,
+    })
+}
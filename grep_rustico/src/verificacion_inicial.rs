@@ -3,9 +3,23 @@ use std::{
     io::{BufRead, BufReader, Lines},
 };
 
+use crate::argumentos::Argumentos;
+use crate::configuracion::Configuracion;
+use crate::dialecto::Dialecto;
 use crate::errors::Error;
 
-const CANTIDAD_ARGUMENTOS: usize = 3;
+const CANTIDAD_ARCHIVOS: usize = 1;
+const SEPARADOR_PATRONES: char = '\n';
+const FIN_DE_OPCIONES: &str = "--";
+const OPCION_IGNORAR_MAYUSCULAS: &str = "-i";
+const OPCION_IGNORAR_MAYUSCULAS_LARGA: &str = "--ignore-case";
+const OPCION_BASICA: &str = "-G";
+const OPCION_EXTENDIDA: &str = "-E";
+const OPCION_PERL: &str = "-P";
+const OPCION_CADENAS_FIJAS: &str = "-F";
+const OPCION_PATRON: &str = "-e";
+const OPCION_ARCHIVO_DE_PATRONES: &str = "-f";
+const OPCION_TEXTO_COMPLETO: &str = "-U";
 
 ///Verifica si se puede procesar el archivo ingresado.
 /// - Si se puede, devuelve un vector con las lineas del archivo.
@@ -29,33 +43,118 @@ pub fn puedo_procesar_archivo(args: &str) -> Result<Vec<String>, Error> {
     }
 }
 
-///Verifica si la cantidad de argumentos ingresados es correcta.
-fn cantidad_correcta_argumentos(cantidad_argumentos: usize) -> bool {
-    cantidad_argumentos == CANTIDAD_ARGUMENTOS
+///Agrega los patrones indicados en un argumento, que pueden ser varios separados
+///por saltos de línea, como en grep.
+fn agregar_patrones(patrones: &mut Vec<String>, argumento: &str) {
+    patrones.extend(argumento.split(SEPARADOR_PATRONES).map(String::from));
 }
 
-///Verifica si la cantidad de argumentos ingresados es correcta.
-/// - Si la cantidad de argumentos es correcta, devuelve un vector con los argumentos.
-/// - Si la cantidad de argumentos no es correcta, devuelve un error de tipo ArgumentosInvalidos.
-/// Una vez que se verifica la cantidad de argumentos, se llama a la función puedo_procesar_archivo.
-pub fn verificar_inicio(args: Vec<String>) -> Result<Vec<String>, Error> {
-    if !cantidad_correcta_argumentos(args.len()) {
+///Separa los argumentos ingresados en la configuración de la expresión regular,
+///los patrones a buscar y el archivo en el que buscarlos. Las opciones son:
+/// - -i (o --ignore-case), para no distinguir mayúsculas de minúsculas.
+/// - -G, -E o -P, para indicar el dialecto de la expresión: básico, extendido o de Perl.
+///   Si se indica más de uno, vale el último, y si no se indica ninguno, es el de Perl.
+/// - -F, para tomar los patrones como cadenas fijas en lugar de expresiones regulares.
+/// - -e PATRON, que se puede repetir, para indicar un patrón aunque comience con un guión.
+/// - -f ARCHIVO, para leer los patrones del archivo, uno por línea.
+///   Una línea vacía es un patrón que coincide con todas las líneas.
+/// - -U, para buscar en todo el texto del archivo, de forma que una coincidencia pueda
+///   abarcar varias líneas. Se muestran todas las líneas que toca cada coincidencia.
+///   También se puede usar con -F.
+/// - --, para indicar que los argumentos que siguen no son opciones.
+///
+///Si no se indica ningún patrón con -e o -f, el patrón es el primer argumento que no es opción.
+/// - Si además del patrón no queda exactamente un archivo, o si falta el valor de una opción,
+///   devuelve un error de tipo ArgumentosInvalidos.
+/// - Si no se puede leer el archivo de patrones, devuelve el error correspondiente.
+pub fn obtener_argumentos(args: Vec<String>) -> Result<Argumentos, Error> {
+    let mut configuracion = Configuracion::default();
+    let mut patrones: Vec<String> = Vec::new();
+    let mut patrones_por_opcion = false;
+    let mut restantes: Vec<String> = Vec::new();
+    let mut solo_restantes = false;
+    let mut iter = args.into_iter().skip(1);
+    while let Some(arg) = iter.next() {
+        if solo_restantes {
+            restantes.push(arg);
+        } else if arg == FIN_DE_OPCIONES {
+            solo_restantes = true;
+        } else if arg == OPCION_IGNORAR_MAYUSCULAS || arg == OPCION_IGNORAR_MAYUSCULAS_LARGA {
+            configuracion.ignorar_mayusculas = true;
+        } else if arg == OPCION_BASICA {
+            configuracion.dialecto = Dialecto::Basico;
+        } else if arg == OPCION_EXTENDIDA {
+            configuracion.dialecto = Dialecto::Extendido;
+        } else if arg == OPCION_PERL {
+            configuracion.dialecto = Dialecto::Perl;
+        } else if arg == OPCION_CADENAS_FIJAS {
+            configuracion.cadenas_fijas = true;
+        } else if arg == OPCION_TEXTO_COMPLETO {
+            configuracion.texto_completo = true;
+        } else if arg == OPCION_PATRON {
+            let patron = iter.next().ok_or(Error::ArgumentosInvalidos)?;
+            agregar_patrones(&mut patrones, &patron);
+            patrones_por_opcion = true;
+        } else if arg == OPCION_ARCHIVO_DE_PATRONES {
+            let archivo = iter.next().ok_or(Error::ArgumentosInvalidos)?;
+            patrones.extend(puedo_procesar_archivo(&archivo)?);
+            patrones_por_opcion = true;
+        } else {
+            restantes.push(arg);
+        }
+    }
+    if !patrones_por_opcion {
+        if restantes.is_empty() {
+            return Err(Error::ArgumentosInvalidos);
+        }
+        agregar_patrones(&mut patrones, &restantes.remove(0));
+    }
+    if restantes.len() != CANTIDAD_ARCHIVOS {
         return Err(Error::ArgumentosInvalidos);
     }
+    Ok(Argumentos {
+        configuracion,
+        patrones,
+        archivo: restantes.remove(0),
+    })
+}
 
-    puedo_procesar_archivo(&args[args.len() - 1])
+///Verifica si los argumentos ingresados son correctos.
+/// - Si lo son, devuelve los argumentos separados junto con las lineas del archivo.
+/// - Si no lo son, devuelve el error que indica obtener_argumentos.
+///
+/// Una vez que se verifican los argumentos, se llama a la función puedo_procesar_archivo.
+pub fn verificar_inicio(args: Vec<String>) -> Result<(Argumentos, Vec<String>), Error> {
+    let argumentos = obtener_argumentos(args)?;
+    let lineas = puedo_procesar_archivo(&argumentos.archivo)?;
+    Ok((argumentos, lineas))
 }
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
 
     #[test]
     fn test01_cantidad_correcta_argumentos() {
-        assert_eq!(cantidad_correcta_argumentos(3), true);
-        assert_eq!(cantidad_correcta_argumentos(2), false);
-        assert_eq!(cantidad_correcta_argumentos(4), false);
+        assert!(obtener_argumentos(vec![
+            "cargo run".to_string(),
+            "abcd".to_string(),
+            "prueba.txt".to_string()
+        ])
+        .is_ok());
+        assert_eq!(
+            obtener_argumentos(vec!["cargo run".to_string(), "abcd".to_string()]),
+            Err(Error::ArgumentosInvalidos)
+        );
+        assert_eq!(
+            obtener_argumentos(vec![
+                "cargo run".to_string(),
+                "a".to_string(),
+                "b.txt".to_string(),
+                "c.txt".to_string()
+            ]),
+            Err(Error::ArgumentosInvalidos)
+        );
     }
 
     ///Este test y el que sigue tiene en el primer assert un archivo de prueba
@@ -67,7 +166,8 @@ mod tests {
                 "cargo run".to_string(),
                 "abcd".to_string(),
                 "src/prueba.txt".to_string()
-            ]),
+            ])
+            .map(|(_, lineas)| lineas),
             Ok(vec![
                 "Hola".to_string(),
                 "esto".to_string(),
@@ -100,4 +200,171 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn test04_obtener_configuracion() {
+        let resultado = obtener_argumentos(vec![
+            "cargo run".to_string(),
+            "-i".to_string(),
+            "error".to_string(),
+            "src/prueba.txt".to_string(),
+        ])
+        .unwrap();
+        assert!(resultado.configuracion.ignorar_mayusculas);
+        assert_eq!(resultado.patrones, vec!["error".to_string()]);
+        assert_eq!(resultado.archivo, "src/prueba.txt".to_string());
+        let resultado = obtener_argumentos(vec![
+            "cargo run".to_string(),
+            "a".to_string(),
+            "b.txt".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(resultado.configuracion, Configuracion::default());
+    }
+
+    #[test]
+    fn test05_obtener_configuracion_con_dialecto() {
+        let resultado = obtener_argumentos(vec![
+            "cargo run".to_string(),
+            "-G".to_string(),
+            "a\\{2\\}".to_string(),
+            "src/prueba.txt".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(resultado.configuracion.dialecto, Dialecto::Basico);
+        assert_eq!(resultado.patrones, vec!["a\\{2\\}".to_string()]);
+        let resultado = obtener_argumentos(vec![
+            "cargo run".to_string(),
+            "-E".to_string(),
+            "-i".to_string(),
+            "a".to_string(),
+            "b.txt".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(resultado.configuracion.dialecto, Dialecto::Extendido);
+        assert!(resultado.configuracion.ignorar_mayusculas);
+        let resultado = obtener_argumentos(vec![
+            "cargo run".to_string(),
+            "-G".to_string(),
+            "-P".to_string(),
+            "a".to_string(),
+            "b.txt".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(resultado.configuracion.dialecto, Dialecto::Perl);
+    }
+
+    #[test]
+    fn test06_obtener_configuracion_con_cadenas_fijas() {
+        let resultado = obtener_argumentos(vec![
+            "cargo run".to_string(),
+            "-F".to_string(),
+            "a.b".to_string(),
+            "src/prueba.txt".to_string(),
+        ])
+        .unwrap();
+        assert!(resultado.configuracion.cadenas_fijas);
+        assert_eq!(resultado.patrones, vec!["a.b".to_string()]);
+        assert_eq!(resultado.archivo, "src/prueba.txt".to_string());
+    }
+
+    #[test]
+    fn test07_varios_patrones_con_opcion_e() {
+        let resultado = obtener_argumentos(vec![
+            "cargo run".to_string(),
+            "-e".to_string(),
+            "-x".to_string(),
+            "-i".to_string(),
+            "-e".to_string(),
+            "hola".to_string(),
+            "src/prueba.txt".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(
+            resultado.patrones,
+            vec!["-x".to_string(), "hola".to_string()]
+        );
+        assert!(resultado.configuracion.ignorar_mayusculas);
+        assert_eq!(resultado.archivo, "src/prueba.txt".to_string());
+        assert_eq!(
+            obtener_argumentos(vec![
+                "cargo run".to_string(),
+                "src/prueba.txt".to_string(),
+                "-e".to_string()
+            ]),
+            Err(Error::ArgumentosInvalidos)
+        );
+    }
+
+    #[test]
+    fn test08_patrones_desde_archivo() {
+        let resultado = obtener_argumentos(vec![
+            "cargo run".to_string(),
+            "-f".to_string(),
+            "src/patrones_prueba.txt".to_string(),
+            "-e".to_string(),
+            "otro".to_string(),
+            "src/prueba.txt".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(
+            resultado.patrones,
+            vec![
+                "una".to_string(),
+                "".to_string(),
+                "-x".to_string(),
+                "otro".to_string()
+            ]
+        );
+        assert_eq!(
+            obtener_argumentos(vec![
+                "cargo run".to_string(),
+                "-f".to_string(),
+                "no_existe.txt".to_string(),
+                "a.txt".to_string()
+            ]),
+            Err(Error::FallaAbrirArchivo)
+        );
+    }
+
+    #[test]
+    fn test09_patrones_separados_y_fin_de_opciones() {
+        let resultado = obtener_argumentos(vec![
+            "cargo run".to_string(),
+            "--".to_string(),
+            "-a\nb".to_string(),
+            "-i".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(resultado.patrones, vec!["-a".to_string(), "b".to_string()]);
+        assert_eq!(resultado.archivo, "-i".to_string());
+        assert!(!resultado.configuracion.ignorar_mayusculas);
+    }
+
+    #[test]
+    fn test10_obtener_configuracion_con_texto_completo() {
+        let resultado = obtener_argumentos(vec![
+            "cargo run".to_string(),
+            "-U".to_string(),
+            "a\\nb".to_string(),
+            "src/prueba.txt".to_string(),
+        ])
+        .unwrap();
+        assert!(resultado.configuracion.texto_completo);
+        assert_eq!(resultado.patrones, vec!["a\\nb".to_string()]);
+    }
+
+    #[test]
+    fn test11_obtener_configuracion_con_cadenas_fijas_y_texto_completo() {
+        let resultado = obtener_argumentos(vec![
+            "cargo run".to_string(),
+            "-F".to_string(),
+            "-U".to_string(),
+            "a.b".to_string(),
+            "src/prueba.txt".to_string(),
+        ])
+        .unwrap();
+        assert!(resultado.configuracion.cadenas_fijas);
+        assert!(resultado.configuracion.texto_completo);
+    }
 }